@@ -22,8 +22,8 @@ struct Child { // basic structure of a component
 #[view] // ... automatically generated with `#[view(tx: async_channel::Sender<Msg>)]`
 impl Child {
 	// `nth` will be the child number ("First" or "Second") and we will communicate
-	// with the parent component through a reference to its transmitter (parent_tx):
-	fn new(nth: &'static str, parent_tx: async_channel::Sender<&'static str>) -> Self {
+	// with the parent component through its transmitter (injected in the view below):
+	fn new(nth: &'static str) -> Self {
 		let (tx, rx) = async_channel::bounded(1);
 		let mut count = 0; // the state
 		
@@ -46,6 +46,9 @@ impl Child {
 	}
 	
 	view![ gtk::Box root {
+		// a clone of the last transmitter of this type provided by a view being expanded:
+		'inject parent_tx: async_channel::Sender<&'static str>
+		
 		orientation: gtk::Orientation::Vertical
 		spacing: 6
 		~
@@ -70,6 +73,9 @@ impl Child {
 }
 
 #[view[ gtk::ApplicationWindow window { // this is the parent component (the composite)
+	// provided to the child components constructed in this view (not passed to each one):
+	'provide parent_tx = tx.clone()
+	
 	application: app
 	title: "Components"
 	titlebar: &gtk::HeaderBar::new()
@@ -83,7 +89,7 @@ impl Child {
 		margin_end: 6
 		~
 		// remember that the component widget is the `root` field:
-		append: &_.root @ Child::new("First", tx.clone()) first_child { }
+		append: &_.root @ Child::new("First") first_child { }
 		// we use composition just to give a variable name
 		
 		append: &second_child.root // or use an argument or variable before view expansion
@@ -105,7 +111,10 @@ impl Child {
 
 fn start(app: &gtk::Application) {
 	let (tx, rx) = async_channel::bounded(1);
-	let second_child = Child::new("Second", tx.clone());
+	let second_child = { // outside a view the transmitter can be provided like this:
+		let _provider = declarative::context::provide(tx.clone());
+		Child::new("Second")
+	};
 	
 	expand_view_here! { }
 	
@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative::{block, clone, component::{self, Component, Glib}, construct, view};
+use gtk::{glib, prelude::*};
+
+// the README example as a component: the library owns the channel and the message loop
+
+enum Msg { Increase, Decrease, Slow }
+
+struct Counter { count: u8, waiting: bool }
+
+#[view(component)] // generates `fn view` from the `view!` below
+impl Component for Counter {
+	type Msg = Msg;
+	type Init = u8;
+	type Root = gtk::Box; // the item named `root`
+	
+	fn init(count: u8) -> Self { Self { count, waiting: false } }
+	
+	fn update(&mut self, msg: Msg) {
+		match msg {
+			Msg::Increase => { self.count = self.count.wrapping_add(1); self.waiting = false }
+			Msg::Decrease => self.count = self.count.wrapping_sub(1),
+			Msg::Slow => self.waiting = true,
+		}
+	}
+	
+	view! {
+		gtk::Box root {
+			orientation: gtk::Orientation::Vertical
+			spacing: 6
+			margin_top: 6
+			margin_bottom: 6
+			margin_start: 6
+			margin_end: 6
+			~
+			append: &_ @ gtk::Label {
+				'bind #set_label: &format!("The count is: {}", state.count)
+			}
+			append: &_ @ gtk::Button::with_label("Increase") {
+				connect_clicked: clone![sender; move |_| sender.send(Msg::Increase)]
+			}
+			append: &_ @ gtk::Button::with_label("Decrease") {
+				connect_clicked: clone![sender; move |_| sender.send(Msg::Decrease)]
+			}
+			append: &_ @ gtk::Button::with_label("Increase in a second") {
+				'bind #set_sensitive: !state.waiting
+				connect_clicked: move |_| {
+					// only senders of components run with `run_sync` can be moved to threads:
+					let Some(sync) = sender.sync() else { return };
+					sender.send(Msg::Slow);
+					
+					std::thread::spawn(move || {
+						std::thread::sleep(std::time::Duration::from_secs(1));
+						sync.send(Msg::Increase)
+					});
+				}
+			}
+		}
+	}
+}
+
+fn start(app: &gtk::Application) {
+	let counter = component::run_sync::<Counter>(&Glib, 0);
+	
+	block! {
+		gtk::ApplicationWindow window {
+			application: app
+			title: "My Application"
+			titlebar: &gtk::HeaderBar::new()
+			child: &counter.root
+		}
+	}
+	window.present()
+}
+
+fn main() -> glib::ExitCode {
+	let app = gtk::Application::default();
+	app.connect_activate(start);
+	app.run()
+}
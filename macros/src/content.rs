@@ -4,9 +4,9 @@
  * SPDX-License-Identifier: (Apache-2.0 or MIT)
  */
 
-use proc_macro2::{Delimiter, Group, TokenStream};
-use quote::quote;
-use syn::{punctuated::Punctuated, visit_mut::VisitMut};
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{parse::discouraged::Speculative, punctuated::Punctuated, visit_mut::VisitMut};
 use crate::{property, Construction};
 
 pub enum Content {
@@ -14,10 +14,15 @@ pub enum Content {
 	BindColon (Box<BindColon>),
 	Construct (Box<Construct>),
 	  Consume (Box<Consume>),
+	  Context (Box<Context>),
+	Directive (Box<Directive>),
 	     Edit (Box<property::Edit>),
+	    Error (syn::Error),
 	       If (Box<(Vec<syn::Attribute>, Vec<If>)>),
 	    Match (Box<Match>),
+	   Parent (Box<Parent>),
 	 Property (Box<property::Property>),
+	    State (Box<State>),
 }
 
 impl syn::parse::Parse for Content {
@@ -51,6 +56,7 @@ pub struct BindColon {
 
 pub struct Construct {
 	  object: bool,
+	   defer: bool,
 	   tilde: syn::Token![~],
 	    last: Option<syn::Token![~]>,
 	pub rest: Vec<Content>,
@@ -65,6 +71,140 @@ pub struct Consume {
 	 expr: syn::Expr,
 }
 
+pub struct Context {
+	attrs: Vec<syn::Attribute>,
+	token: syn::Lifetime,
+	 mut_: Option<syn::Token![mut]>,
+	 name: syn::Ident,
+	   ty: Option<(syn::Token![:], syn::Type)>,
+	 expr: Option<(syn::Token![=], syn::Expr)>,
+}
+
+pub struct Parent {
+	attrs: Vec<syn::Attribute>,
+	token: syn::Lifetime,
+	 name: Option<syn::Ident>,
+	 body: Option<(syn::token::Brace, Vec<Content>)>,
+}
+
+pub struct Directive {
+	 attrs: Vec<syn::Attribute>,
+	 token: syn::Lifetime,
+	tokens: TokenStream,
+	  body: Option<(syn::token::Brace, Vec<Content>)>,
+}
+
+pub struct State {
+	attrs: Vec<syn::Attribute>,
+	token: syn::Lifetime,
+	 name: syn::Ident,
+	colon: syn::Token![:],
+	   ty: syn::Type,
+	equal: syn::Token![=],
+	 expr: syn::Expr,
+}
+
+struct Refresh { name: syn::Ident, captures: Vec<syn::Ident>, stream: TokenStream }
+
+thread_local! {
+	static    STATES: std::cell::RefCell<Vec<Refresh>> = const { std::cell::RefCell::new(vec![]) };
+	static PROVIDERS: std::cell::RefCell<Vec<TokenStream>> = const { std::cell::RefCell::new(vec![]) };
+	static     DEPTH: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+	static   PARENTS: std::cell::RefCell<Vec<syn::Ident>> = const { std::cell::RefCell::new(vec![]) };
+}
+
+pub fn reset() { STATES.take(); PROVIDERS.take(); DEPTH.set(0); PARENTS.take(); }
+
+/// Reports the references declared with 'parent (since the given one) that are used before
+/// every item is constructed, where they do not exist yet.
+pub fn check_parents(since: usize, objects: &mut TokenStream, constrs: &[Construction]) {
+	let mut errors = TokenStream::new();
+	
+	for name in PARENTS.with_borrow_mut(|names| names.split_off(since)) {
+		for stream in std::iter::once(&*objects).chain(constrs.iter().map(Construction::right)) {
+			for span in crate::references(stream, &name) {
+				errors.extend(syn::Error::new(span, format!("`{name}` refers to the parent item with 'parent, \
+					so it can only be used after every item is constructed, not in the construction of an item \
+					(write `~` before the property, or `!` after the `}}` of the item so that its properties \
+					are set after constructing it)")).into_compile_error())
+			}
+		}
+	}
+	objects.extend(errors)
+}
+
+fn cell(name: &syn::Ident, suffix: &str) -> syn::Ident {
+	quote::format_ident!("{name}_{suffix}", span = Span::mixed_site().located_at(name.span()))
+}
+
+fn mentioned(stream: &TokenStream) -> Vec<syn::Ident> {
+	STATES.with_borrow(|states| states.iter()
+		.filter(|state| !crate::references(stream, &state.name).is_empty())
+		.map(|state| state.name.clone()).collect())
+}
+
+fn borrowed(states: &[syn::Ident], stream: TokenStream) -> TokenStream {
+	if states.is_empty() { return stream }
+	let cells = states.iter().map(|name| cell(name, "state"));
+	quote![{ #(#[allow(unused_variables)] let #states = &::std::clone::Clone::clone(&*#cells.borrow());)* #stream }]
+}
+
+fn subscribe(states: &[syn::Ident], assignee: crate::Assignee, stream: TokenStream) {
+	let mut captures: Vec<_> = states.iter().map(|name| cell(name, "state")).collect();
+	captures.extend(assignee.spanned_to(Span::call_site()).next().filter(|root| root != "self"));
+	let stream = borrowed(states, stream);
+	
+	STATES.with_borrow_mut(|refreshes| for refresh in refreshes {
+		if !states.contains(&refresh.name) { continue }
+		refresh.stream.extend(stream.clone());
+		
+		for capture in &captures {
+			if !refresh.captures.contains(capture) { refresh.captures.push(capture.clone()) }
+		}
+	})
+}
+
+pub fn finish(output: &mut TokenStream) {
+	output.extend(PROVIDERS.take()); // provided while the view is constructed
+	
+	for Refresh { name, captures, stream } in STATES.take() {
+		if stream.is_empty() { continue }
+		let (refresh, krate) = (cell(&name, "refresh"), crate::krate());
+		
+		output.extend(quote! { // weak so that the items and the setters in their handlers do not form a cycle
+			let _ = #refresh.set({
+				#(let #captures = #krate::WeakRef::new(&#captures);)*
+				::std::boxed::Box::new(move || {
+					#(let ::std::option::Option::Some(#captures) = #captures.upgrade() else { return };)*
+					#stream
+				})
+			});
+		})
+	}
+}
+
+fn extend_binding(
+	    span: Span,
+	    init: Option<TokenStream>,
+	  stream: TokenStream,
+	settings: &mut TokenStream,
+	bindings: &mut crate::Bindings,
+	assignee: crate::Assignee,
+) {
+	let states = mentioned(&stream);
+	
+	if DEPTH.get() > 0 && !states.is_empty() { return settings.extend(syn::Error::new(span, "a binding that mentions \
+		a 'state can only be in the content of an item, not in that of 'with, 'parent, conditionals or directives \
+		(the state could not refresh it)").into_compile_error()) }
+	
+	if let Some(init) = init { settings.extend(borrowed(&states, init)) }
+	
+	if states.is_empty() {
+		bindings.spans.push(span);
+		bindings.stream.extend(stream)
+	} else { subscribe(&states, assignee, stream) }
+}
+
 pub struct If {
 	else_: Option<syn::Token![else]>,
 	  if_: Option<syn::Token![if]>,
@@ -126,9 +266,11 @@ fn with_attrs(input: syn::parse::ParseStream, attrs: Vec<syn::Attribute>) -> syn
 	if let Ok(tilde) = input.parse::<syn::Token![~]>() {
 		let     last = input.parse()?;
 		let   object = input.parse::<Option<syn::Token![>]>>()?.is_some();
-		let mut rest = vec![]; while !input.is_empty() { rest.push(input.parse()?) }
+		let    defer = !object && input.peek(syn::Token![<]) && input.fork().parse::<syn::TypePath>().is_err();
+		if defer { input.parse::<syn::Token![<]>()?; }
+		let mut rest = vec![]; while !input.is_empty() { rest.push(parse_recovering(input)) }
 		
-		Ok(Content::Construct(Box::new(Construct { object, tilde, last, rest })))
+		Ok(Content::Construct(Box::new(Construct { object, defer, tilde, last, rest })))
 	} else if let Ok(token) = input.parse::<syn::Lifetime>() {
 		if token.ident == "bind" {
 			if input.parse::<syn::Token![:]>().is_ok() {
@@ -154,13 +296,60 @@ fn with_attrs(input: syn::parse::ParseStream, attrs: Vec<syn::Attribute>) -> syn
 		} else if token.ident == "consume" {
 			let  mut_ = input.parse()?;
 			let  name = input.parse()?;
+			
+			let (equal, expr) = if let Ok(arrow) = input.parse::<syn::Token![=>]>() {
+				let callee = input.parse::<syn::ExprPath>()?; // e.g. `'consume effect => Effect::new`
+				let bindings = syn::Ident::new("bindings", arrow.spans[1]);
+				(syn::Token![=](arrow.spans[0]), syn::parse_quote_spanned![arrow.spans[1] => #callee(move || #bindings!())])
+			} else { (input.parse()?, input.parse()?) };
+			
+			let _ = input.parse::<syn::Token![;]>();
+			Ok(Content::Consume(Box::new(Consume { attrs, token, mut_, name, equal, expr })))
+		} else if token.ident == "provide" || token.ident == "inject" {
+			let mut_ = input.parse()?;
+			let name = input.parse()?;
+			
+			let ty = if token.ident == "inject" || input.peek(syn::Token![:]) {
+				Some((input.parse()?, input.parse()?))
+			} else { None };
+			
+			let expr = if token.ident == "provide" || input.peek(syn::Token![=]) {
+				Some((input.parse()?, input.parse()?))
+			} else { None };
+			
+			let _ = input.parse::<syn::Token![;]>();
+			Ok(Content::Context(Box::new(Context { attrs, token, mut_, name, ty, expr })))
+		} else if token.ident == "state" {
+			let  name = input.parse()?;
+			let colon = input.parse()?;
+			let    ty = input.parse()?;
 			let equal = input.parse()?;
 			let  expr = input.parse()?;
 			let     _ = input.parse::<syn::Token![;]>();
-			Ok(Content::Consume(Box::new(Consume { attrs, token, mut_, name, equal, expr })))
-		} else { Err(syn::Error::new(
-			token.span(), format!("expected 'bind, 'consume or maybe 'back, found {token}")
-		)) }
+			Ok(Content::State(Box::new(State { attrs, token, name, colon, ty, equal, expr })))
+		} else if token.ident == "parent" {
+			let name = input.parse::<Option<syn::Ident>>()?;
+			
+			let body = if name.is_none() || input.peek(syn::token::Brace) { Some(parse_vec(input)?) }
+				else { let _ = input.parse::<syn::Token![;]>(); None };
+			
+			Ok(Content::Parent(Box::new(Parent { attrs, token, name, body })))
+		} else {
+			let name = token.ident.to_string();
+			
+			if let "back" | "with" = name.as_str() { Err(syn::Error::new(token.span(), format!(
+				"'{name} can only be written after the arguments of a property"
+			)))? }
+			
+			let mut tokens = TokenStream::new();
+			
+			let body = loop {
+				if input.is_empty() || input.parse::<Option<syn::Token![;]>>()?.is_some() { break None }
+				if input.peek(syn::token::Brace) { break Some(parse_vec(input)?) }
+				tokens.extend([input.parse::<TokenTree>()?])
+			};
+			Ok(Content::Directive(Box::new(Directive { attrs, token, tokens, body })))
+		}
 	} else if input.peek(syn::Token![if]) {
 		let mut vec = vec![input.parse()?];
 		while input.peek(syn::Token![else]) { vec.push(input.parse()?) }
@@ -180,24 +369,70 @@ fn with_attrs(input: syn::parse::ParseStream, attrs: Vec<syn::Attribute>) -> syn
 pub fn parse_vec(input: syn::parse::ParseStream) -> syn::Result<(syn::token::Brace, Vec<Content>)> {
 	let braces;
 	let (brace, mut content) = (syn::braced!(braces in input), vec![]);
-	while !braces.is_empty() { content.push(braces.parse()?) }
+	while !braces.is_empty() { content.push(parse_recovering(&braces)) }
+	check_provides(&mut content);
 	Ok((brace, content))
 }
 
-fn scope(
+/// Rejects each 'provide written after other content, since items are constructed before
+/// properties are set, so it would not be provided in document order.
+pub fn check_provides(content: &mut [Content]) {
+	let Some(first) = content.iter().position(|content| !matches!(content,
+		Content::Context(_) | Content::State(_) | Content::Error(_)
+	)) else { return };
+	
+	for content in &mut content[first..] {
+		let Content::Context(context) = content else { continue };
+		if context.token.ident != "provide" { continue }
+		
+		*content = Content::Error(syn::Error::new(context.token.span(), "'provide can only be written at the \
+			beginning of the content, before the properties, items and other content it is provided to \
+			(items are constructed before properties are set, so elsewhere it would not follow the document order)"))
+	}
+}
+
+pub fn parse_recovering(input: syn::parse::ParseStream) -> Content {
+	let fork = input.fork();
+	
+	match fork.parse() {
+		Ok(content) => { input.advance_to(&fork); content }
+		Err(error) => {
+			let _ = input.parse::<TokenTree>();
+			
+			while !input.is_empty() {
+				if input.parse::<Option<syn::Token![;]>>().is_ok_and(|semi| semi.is_some())
+					|| input.peek(syn::Lifetime)
+					|| input.peek(syn::Token![~])
+					|| input.peek(syn::Token![#])
+					|| input.peek(syn::Ident) && input.peek2(syn::Token![:]) && !input.peek3(syn::Token![:])
+				{ break }
+				let _ = input.parse::<TokenTree>();
+			}
+			Content::Error(error)
+		}
+	}
+}
+
+pub fn scope(
 	content: impl IntoIterator<Item = Content>, attrs: &[syn::Attribute], assignee: crate::Assignee
 ) -> TokenStream {
 	let (mut objects, mut constrs, mut settings, mut bindings) = Default::default();
+	let (providers, parents) = (PROVIDERS.with_borrow(Vec::len), PARENTS.with_borrow(Vec::len));
+	DEPTH.set(DEPTH.get() + 1);
 	
 	for content in content { expand(
 		content, &mut objects, &mut constrs, &mut settings, &mut bindings,
 		&mut None, crate::Attributes::Some(attrs), assignee, None
 	) }
+	DEPTH.set(DEPTH.get() - 1);
 	
 	crate::bindings_error(&mut settings, bindings.spans);
 	
-	for constr in constrs.into_iter().rev() { constr.extend_into(&mut objects) }
-	objects.extend(settings); objects
+	check_parents(parents, &mut objects, &constrs);
+	Construction::extend_all(constrs, &mut objects);
+	objects.extend(settings);
+	objects.extend(PROVIDERS.with_borrow_mut(|drops| drops.split_off(providers))); // provided in this scope
+	objects
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -215,7 +450,14 @@ pub fn expand(
 	match content {
 		Content::Bind(bind) => {
 			let Bind { token, init, mode } = *bind;
-			bindings.spans.push(token.span());
+			
+			if match &mode {
+				BindMode::Unbraced(Content::Property(prop)) => prop.is_constant(),
+				BindMode::Braced { body, .. } => !body.is_empty() && body.iter()
+					.all(|content| matches!(content, Content::Property(prop) if prop.is_constant())),
+				BindMode::Unbraced(_) => false,
+			} { crate::lint::warn(token.span(), "constant_binding", "this binding does not reference any \
+				variable so it never changes (set the property without 'bind)".into()) }
 			
 			match mode {
 				BindMode::Braced { attrs, brace, body } => {
@@ -224,13 +466,11 @@ pub fn expand(
 					
 					let pattrs = pattrs.get(fields);
 					let body = quote![#(#pattrs)* #(#attrs)* #body];
-					if init.is_some() { settings.extend(body.clone()) }
-					bindings.stream.extend(body)
+					extend_binding(token.span(), init.map(|_| body.clone()), body, settings, bindings, assignee)
 				}
 				BindMode::Unbraced(content) => {
 					let scope = scope([content], pattrs.get(fields), assignee);
-					if init.is_some() { settings.extend(scope.clone()) }
-					bindings.stream.extend(scope)
+					extend_binding(token.span(), init.map(|_| scope.clone()), scope, settings, bindings, assignee)
 				}
 			}
 		}
@@ -240,16 +480,17 @@ pub fn expand(
 			body.set_span(brace.span.join());
 			
 			let pattrs = pattrs.get(fields);
-			bindings.spans.push(token.span());
-			bindings.stream.extend(quote![#(#pattrs)* #(#attrs)* #if_ #cond #body]);
-			
-			settings.extend(quote![#(#pattrs)* #(#attrs)* #body])
+			let init = quote![#(#pattrs)* #(#attrs)* #body];
+			extend_binding(token.span(), Some(init), quote![#(#pattrs)* #(#attrs)* #if_ #cond #body], settings, bindings, assignee)
 		}
 		Content::Construct(construct) => {
-			let Construct { object, tilde, last, rest } = *construct;
+			let Construct { object, defer, tilde, last, rest } = *construct;
 			
 			let Some(index) = constr else {
-				let error = crate::ConstrError("only allowed once");
+				let error = "`~` can only be written once, directly in the content of an item expanded \
+					in a builder pattern or as a struct literal (an item is not expanded that way \
+					if `}` is followed by `!` unless it follows a function call, and neither are \
+					'bind, conditional, 'with or non-chained 'back contents)";
 				return objects.extend(syn::Error::new(tilde.span, error).into_compile_error())
 			};
 			
@@ -258,7 +499,8 @@ pub fn expand(
 				Construction::StructLiteral  { span, tilde: t, .. } => (*span, *t) = (tilde.span, last)
 			}
 			
-			if object { constrs.remove(index).extend_into(objects) }
+			let back = matches!(constrs[index], Construction::BuilderPattern { origin: crate::Origin::Back, .. });
+			if object || crate::document() && !defer && !back { constrs.remove(index).extend_into(objects) }
 			
 			for content in rest { expand(
 				content, objects, constrs, settings, bindings, fields, pattrs, assignee, None
@@ -288,9 +530,56 @@ pub fn expand(
 			let let_ = syn::Ident::new("let", token.span());
 			settings.extend(quote![#(#pattrs)* #(#attrs)* #let_ #mut_ #name #equal #expr;]);
 		}
+		Content::Context(context) => {
+			let Context { attrs, token, mut_, name, ty, expr } = *context;
+			let pattrs = pattrs.get(fields);
+			let let_ = syn::Ident::new("let", token.span());
+			let (colon, ty) = ty.unzip();
+			
+			if token.ident == "provide" {
+				let (equal, expr) = expr.unwrap();
+				let (provider, krate) = (quote::format_ident!(
+					"provider_{}", PROVIDERS.with_borrow(Vec::len), span = Span::mixed_site()
+				), crate::krate());
+				
+				objects.extend(quote_spanned! { token.span() =>
+					#(#pattrs)* #(#attrs)* #let_ #mut_ #name #colon #ty #equal #expr;
+					#(#pattrs)* #(#attrs)* #let_ #provider = #krate::context::provide(::core::clone::Clone::clone(&#name));
+				});
+				PROVIDERS.with_borrow_mut(|providers| providers.push(quote![#(#pattrs)* #(#attrs)* ::core::mem::drop(#provider);]))
+			} else {
+				let krate = crate::krate();
+				let inject = match expr {
+					Some((_, expr)) => quote![#krate::context::try_inject::<#ty>().unwrap_or_else(|| #expr)],
+					None => quote![#krate::context::inject::<#ty>()],
+				};
+				objects.extend(quote_spanned![token.span() => #(#pattrs)* #(#attrs)* #let_ #mut_ #name #colon #ty = #inject;])
+			}
+		}
+		Content::Directive(directive) => {
+			let Directive { attrs, token, tokens, body } = *directive;
+			let name = syn::Ident::new(&token.ident.to_string(), token.span());
+			let mut args = quote![#assignee];
+			
+			if let Some(suggestion) = crate::suggest(&token.ident.to_string(), &[
+				"bind", "consume", "inject", "parent", "provide", "state", "back", "with"
+			]) { crate::lint::warn(token.span(), "similar_directive", format!("{token} is expanded as a call \
+				to the custom macro `{name}!`, but it is close to the built-in '{suggestion} (rename the macro if it is not a typo)")) }
+			
+			if !tokens.is_empty() || body.is_some() { args.extend(quote![, #tokens]) }
+			
+			if let Some((brace, body)) = body {
+				let mut body = Group::new(Delimiter::Brace, scope(body, &[], assignee));
+				body.set_span(brace.span.join());
+				args.extend([TokenTree::Group(body)])
+			}
+			let pattrs = pattrs.get(fields);
+			settings.extend(quote![#(#pattrs)* #(#attrs)* #name!(#args);])
+		}
 		Content::Edit(edit) => property::expand_edit(
 			*edit, objects, constrs, settings, bindings, fields, pattrs, assignee
 		),
+		Content::Error(error) => objects.extend(error.into_compile_error()),
 		Content::If(if_) => {
 			let (pattrs, (attrs, if_vec)) = (pattrs.get(fields), *if_);
 			settings.extend(quote![#(#pattrs)* #(#attrs)*]);
@@ -316,6 +605,63 @@ pub fn expand(
 			let mut body = Group::new(Delimiter::Brace, body); body.set_span(brace.span.join());
 			settings.extend(quote![#(#pattrs)* #(#attrs)* #token #expr #body]);
 		}
+		Content::Parent(parent) => {
+			let Parent { attrs, token, name, body } = *parent;
+			
+			let Some(mut parent) = crate::item::parent() else {
+				return objects.extend(syn::Error::new(token.span(), "'parent can only be used \
+					in the content of a child item (this one has no parent in the view)").into_compile_error())
+			};
+			for ident in &mut parent { ident.set_span(ident.span().located_at(token.span())) }
+			
+			let pattrs = pattrs.get(fields);
+			let let_ = syn::Ident::new("let", token.span());
+			
+			if let Some(name) = name {
+				PARENTS.with_borrow_mut(|names| names.push(name.clone()));
+				settings.extend(quote![#(#pattrs)* #(#attrs)* #let_ #name = &#parent;])
+			}
+			
+			if let Some((brace, body)) = body {
+				let assignee = crate::Assignee::Field(None, &parent);
+				let mut body = Group::new(Delimiter::Brace, scope(body, &[], assignee));
+				body.set_span(brace.span.join());
+				settings.extend(quote![#(#pattrs)* #(#attrs)* #body])
+			}
+		}
+		Content::State(state) => {
+			let State { attrs, token, name, colon, ty, equal, expr } = *state;
+			
+			if DEPTH.get() > 0 { return objects.extend(syn::Error::new(token.span(), "'state can only be \
+				declared in the content of an item, not in that of 'bind, 'with, 'parent, conditionals or directives \
+				(its setters would not be visible to the rest of the view)").into_compile_error()) }
+			
+			let (state, refresh) = (cell(&name, "state"), cell(&name, "refresh"));
+			let (set, update) = (quote::format_ident!("set_{name}"), quote::format_ident!("update_{name}"));
+			
+			let pattrs = pattrs.get(fields);
+			let let_ = syn::Ident::new("let", token.span());
+			
+			objects.extend(quote! {
+				#(#pattrs)* #(#attrs)* #let_ #state #colon ::std::rc::Rc<::std::cell::RefCell<#ty>>
+					#equal ::std::rc::Rc::new(::std::cell::RefCell::new(#expr));
+				
+				#(#pattrs)* #(#attrs)* #let_ #refresh = ::std::rc::Rc::new(
+					::std::cell::OnceCell::<::std::boxed::Box<dyn Fn()>>::new()
+				);
+				#(#pattrs)* #(#attrs)* #[allow(unused_variables)] #let_ #set = {
+					let (state, refresh) = (#state.clone(), #refresh.clone());
+					move |value: #ty| { *state.borrow_mut() = value; if let Some(refresh) = refresh.get() { refresh() } }
+				};
+				#(#pattrs)* #(#attrs)* #[allow(unused_variables)] #let_ #update = {
+					let (state, refresh) = (#state.clone(), #refresh.clone());
+					move |update: &mut dyn FnMut(&mut #ty)| {
+						update(&mut state.borrow_mut()); if let Some(refresh) = refresh.get() { refresh() }
+					}
+				};
+			});
+			STATES.with_borrow_mut(|states| states.push(Refresh { name, captures: vec![], stream: TokenStream::new() }))
+		}
 		Content::Property(prop) => property::expand(
 			*prop, objects, constrs, settings, bindings, fields, pattrs, assignee, constr
 		)
@@ -7,7 +7,7 @@
 use proc_macro2::{Group, Punct, Spacing, Span, TokenStream};
 use quote::{TokenStreamExt, quote, quote_spanned};
 use syn::punctuated::Punctuated;
-use crate::{content, Assignee, Attributes, Construction};
+use crate::{content, Assignee, Attributes, Construction, Origin};
 
 struct Field {
 	 vis: Option<syn::Visibility>,
@@ -17,6 +17,52 @@ struct Field {
 	auto: bool,
 }
 
+thread_local![static COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) }];
+
+pub fn reset_names() { COUNT.set(0); INFERRED.take(); PARAMS.take(); PARENTS.take(); }
+
+thread_local! {
+	static INFERRED: std::cell::RefCell<Option<Vec<syn::Ident>>> = const { std::cell::RefCell::new(None) };
+	static PARAMS: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(vec![]) };
+}
+
+/// Allows (or not) the types of the following exported items to be inferred until they are taken.
+pub fn start_inference(allowed: bool) { INFERRED.set(allowed.then(Vec::new)) }
+
+pub fn take_inferred() -> Vec<syn::Ident> { INFERRED.take().unwrap_or_default() }
+
+thread_local![static PARENTS: std::cell::RefCell<Vec<Punctuated<syn::Ident, syn::Token![.]>>> = const { std::cell::RefCell::new(vec![]) }];
+
+pub fn parent() -> Option<Punctuated<syn::Ident, syn::Token![.]>> {
+	PARENTS.with_borrow(|parents| parents.len().checked_sub(2).map(|index| parents[index].clone()))
+}
+
+pub fn enter(assignee: &Assignee) {
+	PARENTS.with_borrow_mut(|parents| parents.push(assignee.spanned_to(Span::call_site()).collect()))
+}
+
+pub fn leave() { PARENTS.with_borrow_mut(|parents| parents.pop()); }
+
+fn infer(name: &syn::Ident) -> Option<syn::TypePath> {
+	if INFERRED.with_borrow(Option::is_none) { return None }
+	let mut param = String::from("__");
+	
+	for word in name.to_string().split('_').filter(|word| !word.is_empty()) {
+		let mut chars = word.chars();
+		param.extend(chars.next().map(|char| char.to_ascii_uppercase()));
+		param.extend(chars)
+	}
+	let param = PARAMS.with_borrow_mut(|params| { // `a_b` and `aB` would be the same
+		let unique = (0..).map(|n| if n == 0 { param.clone() } else { format!("{param}{n}") })
+			.find(|unique| !params.contains(unique)).unwrap();
+		params.push(unique.clone());
+		unique
+	});
+	let param = syn::Ident::new(&param, Span::mixed_site().located_at(name.span()));
+	INFERRED.with_borrow_mut(|inferred| inferred.as_mut().map(|inferred| inferred.push(param.clone())));
+	Some(syn::TypePath { qself: None, path: param.into() })
+}
+
 fn parse_field(
 	display: Option<&dyn std::fmt::Display>, input: syn::parse::ParseStream, span: Span
 ) -> syn::Result<Field> {
@@ -36,15 +82,14 @@ fn parse_field(
 		}
 	}
 	
-	thread_local![static COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) }];
-	
 	let (name, auto) = if vis.is_some() { (name?, false) } else {
 		name.map(|name| (name, false)).unwrap_or_else(|_| (syn::Ident::new(&{
 			let count = COUNT.replace(COUNT.get().wrapping_add(1));
 			compact_str::format_compact!("{}{count}", display.unwrap_or(&Name(ty.as_deref())))
-		}, span), true))
+		}, Span::mixed_site().located_at(span)), true))
 	};
 	
+	if vis.is_none() && !auto { crate::lint::name(&name, display.is_none()) }
 	Ok(Field { vis, mut_, name, ty, auto })
 }
 
@@ -60,6 +105,7 @@ pub struct Item {
 	 object: Object,
 	   mode: Mode,
 	   body: Vec<content::Content>,
+	   rest: Option<syn::Token![..]>,
 }
 
 impl Item {
@@ -82,17 +128,24 @@ pub fn parse(input: syn::parse::ParseStream, attrs: Option<Vec<syn::Attribute>>)
 		(group.is_none(), Object::Path(Box::new(Path { path, group, field })))
 	} else { (false, Object::Ref(crate::parse_unterminated(input)?)) };
 	
-	let (mut body, mut mode) = (vec![], Mode::Normal(Span::call_site()));
+	let (mut body, mut mode, mut rest) = (vec![], Mode::Normal(Span::call_site()), None);
 	
 	if input.peek(syn::token::Brace) {
 		let braces;
 		let brace = syn::braced!(braces in input);
 		
 		if let Object::Path(path) = &mut object {
-			if path.field.auto { path.field.name.set_span(brace.span.open()) }
+			if path.field.auto {
+				path.field.name.set_span(Span::mixed_site().located_at(brace.span.open()))
+			}
 		}
 		
-		while !braces.is_empty() { body.push(braces.parse()?) }
+		while !braces.is_empty() {
+			if let Some(dots) = braces.parse::<Option<syn::Token![..]>>()? {
+				if rest.replace(dots).is_some() { Err(syn::Error::new_spanned(dots, "`..` was already written"))? }
+			} else { body.push(content::parse_recovering(&braces)) }
+		}
+		content::check_provides(&mut body);
 		
 		mode = if let Some(Ok(tilde)) = literable.then(|| input.parse::<syn::Token![?]>()) {
 			Mode::StructLiteral(tilde)
@@ -105,14 +158,19 @@ pub fn parse(input: syn::parse::ParseStream, attrs: Option<Vec<syn::Attribute>>)
 			if path.group.is_some() { Mode::Normal(brace.span.close()) }
 			else { Mode::Builder(brace.span.close()) }
 		} else { Mode::Builder(brace.span.close()) };
+		
+		if let (Some(dots), false) = (rest, matches!(mode, Mode::StructLiteral(_))) {
+			Err(syn::Error::new_spanned(dots, "`..` can only fill the remaining fields \
+				of a type that is expanded as a struct literal (with `?` after `}`)"))?
+		}
 	}
 	
-	Ok(Item { attrs, at_span, object, mode, body })
+	Ok(Item { attrs, at_span, object, mode, body, rest })
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn expand(
-	Item { attrs, at_span, object, mode, body }: Item,
+	Item { attrs, at_span, object, mode, body, rest }: Item,
 	 objects: &mut TokenStream,
 	 constrs: &mut Vec<Construction>,
 	settings: &mut TokenStream,
@@ -123,6 +181,14 @@ pub fn expand(
 	let mut attrs = attrs.unwrap();
 	crate::extend_attributes(&mut attrs, pattrs.get(fields));
 	
+	if !matches!(&object, Object::Path(path) if path.field.vis.is_some()) {
+		if let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("field")) {
+			objects.extend(syn::Error::new_spanned(attr, crate::FIELD_ERROR).into_compile_error())
+		}
+	}
+	let (attrs, field_attrs) = crate::target_attributes(attrs)
+		.unwrap_or_else(|error| { objects.extend(error.into_compile_error()); Default::default() });
+	
 	let let_ = syn::Ident::new("let", at_span);
 	let (attributes, assignee_field, assignee_ident);
 	
@@ -134,11 +200,12 @@ pub fn expand(
 			let constr = match mode {
 				Mode::Builder(span) => {
 					constrs.push(Construction::BuilderPattern {
-						 left: quote![#(#attrs)* #let_ #mut_ #name =],
-						right: group.as_ref().map(|group| quote![#path #group])
+						  left: quote![#(#attrs)* #let_ #mut_ #name =],
+						 right: group.as_ref().map(|group| quote![#path #group])
 							.unwrap_or_else(|| quote_spanned![span => #path =>]),
-						 span,
-						tilde: None,
+						  span,
+						 tilde: None,
+						origin: if group.is_some() { Origin::Call } else { Origin::Braces },
 					});
 					
 					Some(constrs.len() - 1)
@@ -148,6 +215,7 @@ pub fn expand(
 						  left: quote![#(#attrs)* #let_ #mut_ #name =],
 						    ty: quote![#path],
 						fields: Default::default(),
+						  rest,
 						  span: question.span,
 						 tilde: None,
 					});
@@ -173,7 +241,10 @@ pub fn expand(
 					break 'block attributes = crate::Attributes::Some(attrs)
 				};
 				
-				attributes = crate::Attributes::None(fields.len());
+				let attrs = match field_attrs {
+					Some(field_attrs) => { attributes = crate::Attributes::Some(attrs); field_attrs }
+					None => { attributes = crate::Attributes::None(fields.len()); attrs }
+				};
 				
 				let ty = 'ty: {
 					if let Some(ty) = ty { break 'ty *ty }
@@ -190,6 +261,8 @@ pub fn expand(
 						path => path
 					};
 					
+					if let Some(ty) = group.as_ref().and_then(|_| infer(&name)) { break 'ty ty }
+					
 					let error = syn::Error::new_spanned(quote![#path #group], NO_TYPE_ERROR);
 					break 'block objects.extend(error.into_compile_error())
 				};
@@ -213,10 +286,18 @@ pub fn expand(
 		}
 	};
 	
+	enter(&new_assignee);
+	let tilde = body.iter().any(|content| matches!(content, content::Content::Construct(_)));
+	
 	for content in body { content::expand(
 		content, objects, constrs, settings, bindings, fields,
 		attributes.as_slice(), new_assignee, new_constr
 	) }
+	leave();
+	
+	if let (true, false, Some(index)) = (crate::document(), tilde, new_constr) {
+		constrs.remove(index).extend_into(objects)
+	}
 }
 
 pub struct Back {
@@ -237,10 +318,11 @@ pub fn parse_back(input: syn::parse::ParseStream) -> syn::Result<Option<Box<Back
 	let brace = syn::braced!(braces in input);
 	let build = input.parse::<syn::Token![!]>().err().map(|_| brace.span.join());
 	
-	if field.auto { field.name.set_span(brace.span.open()) }
+	if field.auto { field.name.set_span(Span::mixed_site().located_at(brace.span.open())) }
 	
 	let mut body = vec![];
-	while !braces.is_empty() { body.push(braces.parse()?) }
+	while !braces.is_empty() { body.push(content::parse_recovering(&braces)) }
+	content::check_provides(&mut body);
 	
 	Ok(Some(Box::new(Back { token, field, body, build })))
 }
@@ -249,9 +331,14 @@ fn builds(content: Option<&content::Content>) -> bool {
 	content.map(|content| match content {
 		| content::Content::Bind(_)
 		| content::Content::BindColon(_)
+		| content::Content::Context(_)
+		| content::Content::Directive(_)
 		| content::Content::Edit(_)
+		| content::Content::Error(_)
 		| content::Content::If(_)
-		| content::Content::Match(_) => false,
+		| content::Content::Match(_)
+		| content::Content::Parent(_)
+		| content::Content::State(_) => false,
 		
 		| content::Content::Consume(_)
 		| content::Content::Property(_) => true,
@@ -271,24 +358,42 @@ pub fn expand_back(
 	   attrs: Attributes<Vec<syn::Attribute>>,
 	   right: TokenStream,
 ) {
+	let Field { vis, mut_, name, ty, auto } = field;
+	
+	let (attrs, field_attrs) = match attrs {
+		Attributes::Some(attrs) => {
+			if let (None, Some(attr)) = (&vis, attrs.iter().find(|attr| attr.path().is_ident("field"))) {
+				objects.extend(syn::Error::new_spanned(attr, crate::FIELD_ERROR).into_compile_error())
+			}
+			let (attrs, field_attrs) = crate::target_attributes(attrs)
+				.unwrap_or_else(|error| { objects.extend(error.into_compile_error()); Default::default() });
+			(Attributes::Some(attrs), field_attrs)
+		}
+		attrs => (attrs, None)
+	};
+	
 	let pattrs = attrs.get(fields);
 	let let_ = syn::Ident::new("let", token.span());
-	let Field { vis, mut_, name, ty, auto } = field;
 	
-	let left = if auto && build.is_some() && builds(body.first()) && builds(body.last())
+	let chains = crate::strict() || body.iter().all(|content| match content {
+		content::Content::Property(prop) => prop.chains(), _ => true
+	});
+	let left = if auto && build.is_some() && chains && builds(body.first()) && builds(body.last())
 		{ quote![#(#pattrs)*] } else { quote![#(#pattrs)* #let_ #mut_ #name =] };
 	
 	let index = if let Some(span) = build {
-		constrs.push(Construction::BuilderPattern { left, right, span, tilde: None });
+		constrs.push(Construction::BuilderPattern { left, right, span, tilde: None, origin: Origin::Back });
 		Some(constrs.len() - 1)
 	} else { settings.extend(quote![#left #right;]); None };
 	
 	let mut setup = TokenStream::new();
+	enter(&Assignee::Ident(None, &name));
 	
 	for content in body { content::expand(
 		content, objects, constrs, &mut setup, bindings,
 		fields, attrs.as_slice(), Assignee::Ident(None, &name), index
 	) }
+	leave();
 	
 	'block: {
 		let Some(vis) = vis else { break 'block };
@@ -296,13 +401,13 @@ pub fn expand_back(
 			let error = syn::Error::new_spanned(quote![#vis #ty], NO_FIELD_ERROR);
 			break 'block objects.extend(error.into_compile_error())
 		};
-		let Some(ty) = ty else {
-			let error = syn::Error::new_spanned(quote![#name], NO_TYPE_ERROR);
+		let Some(ty) = ty.or_else(|| infer(&name).map(Box::new)) else {
+			let error = syn::Error::new_spanned(&name, NO_TYPE_ERROR);
 			break 'block objects.extend(error.into_compile_error())
 		};
-		let attrs = match attrs {
-			Attributes::Some(attrs) => attrs,
-			Attributes::None(index) => fields.iter().nth(index).unwrap().attrs.clone()
+		let attrs = match (field_attrs, attrs) {
+			(Some(attrs), _) | (None, Attributes::Some(attrs)) => attrs,
+			(None, Attributes::None(index)) => fields.iter().nth(index).unwrap().attrs.clone()
 		};
 		fields.push(syn::Field {
 			attrs, vis, ty: syn::Type::Path(*ty),
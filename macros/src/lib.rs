@@ -8,7 +8,10 @@
 
 mod content;
 mod item;
+mod lint;
+mod observable;
 mod property;
+mod props;
 mod view;
 
 use proc_macro::TokenStream;
@@ -36,18 +39,42 @@ use syn::{punctuated::Punctuated, visit_mut::VisitMut};
 /// }
 /// ~~~
 pub fn block(stream: TokenStream) -> TokenStream {
+	item::reset_names();
+	content::reset();
+	lint::start();
+	
 	if stream.is_empty() {
 		let error = syn::Error::new(Span::call_site(), "this view block has no content");
 		return TokenStream::from(error.into_compile_error())
 	}
 	
 	let mut structs = vec![];
-	let view::Streaming::Roots(roots) = syn::parse_macro_input!(stream) else { panic!() };
-	let (mut stream, bindings) = view::expand(&mut structs, roots, false);
+	let view::Block { lint, document, strict, krate, yields, roots } = syn::parse_macro_input!(stream);
+	DOCUMENT.set(document); STRICT.set(strict); CRATE.set(krate);
+	let (mut stream, mut bindings) = view::expand(&mut structs, roots, true);
+	
+	let Some(mut yields) = yields else {
+		bindings_error(&mut stream, bindings.spans);
+		for strukt in structs { strukt.to_tokens(&mut stream) }
+		lint::finish(&mut stream, lint, None);
+		return TokenStream::from(stream)
+	};
+	
+	if !bindings.spans.is_empty() {
+		let mut visitor = Visitor::Ok {
+			items: None, assignee: &mut None, placeholder: "bindings", stream: &mut bindings.stream
+		};
+		visitor.visit_expr_mut(&mut yields);
+		
+		match visitor.stream_is_empty() {
+			Ok(empty) => if !empty { bindings_error(&mut stream, bindings.spans) },
+			Err(error) => stream.extend(error.into_compile_error()),
+		}
+	}
 	
-	bindings_error(&mut stream, bindings.spans);
 	for strukt in structs { strukt.to_tokens(&mut stream) }
-	TokenStream::from(stream)
+	lint::finish(&mut stream, lint, None);
+	TokenStream::from(quote::quote![{ #stream #yields }])
 }
 
 #[proc_macro_attribute]
@@ -89,25 +116,43 @@ pub fn block(stream: TokenStream) -> TokenStream {
 /// }
 /// ~~~
 pub fn view(stream: TokenStream, code: TokenStream) -> TokenStream {
+	item::reset_names();
+	content::reset();
+	lint::start();
+	
+	let sources = [TokenStream2::from(stream.clone()), TokenStream2::from(code.clone())];
 	let item = &mut syn::parse_macro_input!(code);
+	let view::Args { lint: enabled, document, strict, component, krate, derive, placeholder, streaming } = syn::parse_macro_input!(stream);
+	DOCUMENT.set(document); STRICT.set(strict); CRATE.set(krate);
+	let placeholder = placeholder.map_or_else(|| "expand_view_here".into(), |ident| ident.to_string());
+	let derive: Option<syn::Attribute> = (!derive.is_empty()).then(|| syn::parse_quote![#[derive(#(#derive),*)]]);
 	let mut output = TokenStream2::new();
 	
-	let fill = |item: &mut _, output: &mut _, structs: &mut Vec<_>| {
+	let fill = |item: &mut _, output: &mut TokenStream2, structs: &mut Vec<syn::ItemStruct>| {
+		if let Some(strukt) = structs.first().filter(|strukt| strukt.ident == "_") {
+			let span = strukt.ident.span();
+			structs.clear();
+			return output.extend(syn::Error::new(span, "the struct of this view could not be \
+				named after an `impl` (the type must be a path like `Name` or `self::Name<T>`), so \
+				it must be named in the attribute (e.g. `#[view(struct = Name)]`)").into_compile_error())
+		}
 		if let syn::Item::Mod(mod_) = item {
 			if let Some((_, items)) = &mut mod_.content {
 				items.reserve(structs.len());
-				while let Some(item) = structs.pop() {
+				while let Some(mut item) = structs.pop() {
+					item.attrs.extend(derive.clone());
 					items.push(syn::Item::Struct(item))
 				} return
 			}
 		}
-		while let Some(item) = structs.pop() { item.to_tokens(output) }
+		while let Some(mut item) = structs.pop() { item.attrs.extend(derive.clone()); item.to_tokens(output) }
 	};
 	
-	match syn::parse_macro_input!(stream) {
+	match streaming {
 		view::Streaming::Struct { vis, ident, generics, fields } => {
 			let errable = !matches!(vis, syn::Visibility::Inherited)
 				|| ident.is_some() || generics.lt_token.is_some() || !fields.is_empty();
+			let n_fields = fields.len();
 			
 			let structs = vec![syn::ItemStruct {
 				vis, fields: syn::Fields::Named(syn::FieldsNamed {
@@ -118,34 +163,94 @@ pub fn view(stream: TokenStream, code: TokenStream) -> TokenStream {
 				generics, semi_token: Default::default(),
 			}];
 			
-			let mut visitor = view::Visitor::Ok { structs, errable, deque: Default::default() };
+			let mut visitor = view::Visitor::Ok {
+				structs, component, deque: Default::default(), views: 0, inferable: true, placeholder: &placeholder
+			};
 			visitor.visit_item_mut(item);
 			
 			match visitor {
-				view::Visitor::Ok { mut structs, mut deque, .. } => {
-					if deque.is_empty() {
+				view::Visitor::Ok { mut structs, mut deque, views, .. } => {
+					if views == 0 {
 						let error = syn::Error::new(Span::call_site(), "if no view code is \
 							written as the content of this attribute, at least one view must \
-							be created with `view!` in the scope of a `mod`, `impl` or `trait`");
+							be created with `view!` in the scope of a `mod`, `impl`, `trait` or function body");
 						return TokenStream::from(error.into_compile_error())
 					}
+					let syn::Fields::Named(fields) = &structs[0].fields else { panic!() };
+					
+					if fields.named.len() == n_fields { // no view exported items to it
+						structs.swap_remove(0);
+						if errable {
+							output.extend(syn::Error::new(Span::call_site(), "these arguments would \
+								declare a struct that does not reference any item in the view").into_compile_error())
+						}
+					}
 					while let Some((spans, stream, bindings)) = deque.pop_front() {
-						view::parse(item, &mut output, spans, stream, bindings);
+						view::parse(&mut |visitor| visitor.visit_item_mut(item), &mut output, spans, stream, bindings, &placeholder);
 						fill(item, &mut output, &mut structs)
 					}
+					fill(item, &mut output, &mut structs)
 				}
 				view::Visitor::Error(error) => return TokenStream::from(error.into_compile_error())
 			}
 		}
 		view::Streaming::Roots(roots) => {
 			let (range, mut structs) = (Range(Span::call_site(), Span::call_site()), vec![]);
-			let (stream, bindings) = view::expand(&mut structs, roots, false);
-			view::parse(item, &mut output, range, stream, bindings);
+			let (stream, bindings) = view::expand(&mut structs, roots, true);
+			view::parse(&mut |visitor| visitor.visit_item_mut(item), &mut output, range, stream, bindings, &placeholder);
 			fill(item, &mut output, &mut structs)
 		}
 	}
 	
-	item.to_tokens(&mut output); TokenStream::from(output)
+	item.to_tokens(&mut output);
+	let mut views = vec![sources[0].clone()];
+	let outside = lint::split(sources[1].clone(), &mut views);
+	lint::finish(&mut output, enabled, Some((&outside, &views)));
+	TokenStream::from(output)
+}
+
+#[proc_macro_derive(Props, attributes(prop))]
+/// Allows a struct to be defined as a struct literal item (with `?` after `}`) whose
+/// missing fields are filled in with their defaults when `..` is written in the item.
+///
+/// Fields marked with `#[prop(default)]` or `#[prop(optional)]` are given `Default::default()`,
+/// fields marked with `#[prop(default = expr)]` are given `expr` and the rest are required,
+/// so that each missing one is reported at the item as a compilation error.
+///
+/// ### Basic usage
+///
+/// ~~~
+/// use declarative_macros::{block as view, Props};
+///
+/// macro_rules! construct { // what `declarative::construct!` does for `..`
+///     (? $type:ty { $($field:ident: $value:expr,)* .. }) => {
+///         <$type>::props() $(.$field($value))* .build().start()
+///     }
+/// }
+///
+/// #[derive(Props)]
+/// struct Greeting {
+///     name: &'static str, // required
+///     #[prop(default = "Hello")] salute: &'static str,
+///     #[prop(optional)] excited: bool,
+/// }
+///
+/// impl Greeting {
+///     fn start(self) -> String {
+///         format!("{}, {}{}", self.salute, self.name, if self.excited { "!" } else { "." })
+///     }
+/// }
+///
+/// fn usage() -> String {
+///     view! {
+///         Greeting greeting { name: "world"; excited: true; .. }?
+///     }
+///     greeting // Hello, world!
+/// }
+/// ~~~
+pub fn props(stream: TokenStream) -> TokenStream {
+	let input = syn::parse_macro_input!(stream);
+	TokenStream::from(props::derive(input).unwrap_or_else(syn::Error::into_compile_error))
 }
 
 #[derive(Copy, Clone)]
@@ -162,20 +267,25 @@ struct Bindings { spans: Vec<Span>, stream: TokenStream2 }
 
 enum Construction {
 	BuilderPattern {
-		 left: TokenStream2,
-		right: TokenStream2,
-		 span: Span,
-		tilde: Option<syn::Token![~]>,
+		  left: TokenStream2,
+		 right: TokenStream2,
+		  span: Span,
+		 tilde: Option<syn::Token![~]>,
+		origin: Origin,
 	},
 	StructLiteral {
 		  left: TokenStream2,
 		    ty: TokenStream2,
 		fields: TokenStream2,
+		  rest: Option<syn::Token![..]>,
 		  span: Span,
 		 tilde: Option<syn::Token![~]>,
 	},
 }
 
+#[derive(Copy, Clone)]
+enum Origin { Braces, Call, Back }
+
 enum Path {
 	Type(syn::TypePath), Field {
 		access: Punctuated<syn::Ident, syn::Token![.]>,
@@ -189,12 +299,163 @@ enum Visitor<'a, 'b> {
 	#[allow(clippy::type_complexity)]
 	Ok {  items: Option<&'a mut Map<Iter<'b, item::Item>, fn(&item::Item) -> Assignee>>,
 	   assignee: &'a mut Option<Assignee<'b>>,
-	placeholder: &'static str,
+	placeholder: &'a str,
 	     stream: &'a mut TokenStream2 },
 	
 	Error(syn::Error)
 }
 
+thread_local! {
+	static DOCUMENT: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+	static   STRICT: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+	static    CRATE: std::cell::RefCell<Option<syn::Path>> = const { std::cell::RefCell::new(None) };
+}
+
+fn document() -> bool { DOCUMENT.get() }
+
+fn strict() -> bool { STRICT.get() }
+
+/// The path of the `declarative` crate for the code that uses its modules.
+fn krate() -> TokenStream2 {
+	CRATE.with_borrow(|krate| krate.as_ref().map_or_else(|| quote::quote![::declarative], ToTokens::to_token_stream))
+}
+
+/// Finds where some code refers to the variable `name` (not to a field, method, path segment
+/// or macro), except where a closure parameter or a `let` of that code with the same name shadows it.
+fn references(stream: &TokenStream2, name: &syn::Ident) -> Vec<Span> {
+	let mut scan = Scan { name, shadowed: false, spans: vec![] };
+	scan.tokens(stream.clone());
+	scan.spans
+}
+
+struct Scan<'a> { name: &'a syn::Ident, shadowed: bool, spans: Vec<Span> }
+
+impl Scan<'_> {
+	fn tokens(&mut self, stream: TokenStream2) {
+		use syn::{parse::Parser, visit::Visit};
+		
+		if let Ok(stmts) = syn::Block::parse_within.parse2(stream.clone()) {
+			let shadowed = self.shadowed;
+			for stmt in &stmts { self.visit_stmt(stmt) }
+			return self.shadowed = shadowed
+		}
+		if let Ok(exprs) = Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated.parse2(stream.clone()) {
+			return for expr in &exprs { self.visit_expr(expr) }
+		}
+		self.trees(stream) // not Rust code (e.g. the arguments of `construct!`)
+	}
+	
+	fn trees(&mut self, stream: TokenStream2) {
+		use proc_macro2::TokenTree;
+		
+		let mut trees = stream.into_iter().peekable();
+		let (mut after, mut colons) = (None, 0); // a variable is not a field, method, path segment or macro
+		
+		while let Some(tree) = trees.next() {
+			match &tree {
+				TokenTree::Group(group) => self.tokens(group.stream()),
+				TokenTree::Ident(ident) => if ident == self.name && !self.shadowed && colons < 2 && !matches!(after, Some('.' | '\''))
+					&& !matches!(trees.peek(), Some(TokenTree::Punct(punct)) if matches!(punct.as_char(), ':' | '!'))
+				{ self.spans.push(ident.span()) }
+				TokenTree::Literal(literal) => self.literal(literal.to_string(), literal.span()),
+				TokenTree::Punct(_) => ()
+			}
+			(after, colons) = match tree {
+				TokenTree::Punct(punct) if punct.as_char() == ':' => (Some(':'), colons + 1),
+				TokenTree::Punct(punct) => (Some(punct.as_char()), 0),
+				_ => (None, 0),
+			};
+		}
+	}
+	
+	fn literal(&mut self, literal: String, span: Span) {
+		if !self.shadowed && literal.split('{').skip(1) // captured by `format!`
+			.any(|part| part.strip_prefix(&*self.name.to_string()).is_some_and(|rest| rest.starts_with(['}', ':'])))
+		{ self.spans.push(span) }
+	}
+	
+	fn binds(&self, pat: &syn::Pat) -> bool {
+		struct Binds<'a>(&'a syn::Ident, bool);
+		
+		impl syn::visit::Visit<'_> for Binds<'_> {
+			fn visit_pat_ident(&mut self, node: &syn::PatIdent) {
+				self.1 |= node.ident == *self.0;
+				syn::visit::visit_pat_ident(self, node)
+			}
+		}
+		let mut binds = Binds(self.name, false);
+		syn::visit::Visit::visit_pat(&mut binds, pat);
+		binds.1
+	}
+	
+	fn shadow(&mut self, pats: &[&syn::Pat], visit: impl FnOnce(&mut Self)) {
+		let shadowed = self.shadowed;
+		self.shadowed |= pats.iter().any(|pat| self.binds(pat));
+		visit(self);
+		self.shadowed = shadowed
+	}
+}
+
+impl<'ast> syn::visit::Visit<'ast> for Scan<'_> {
+	fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+		if !self.shadowed && node.qself.is_none() && node.path.get_ident() == Some(self.name) {
+			self.spans.push(node.path.segments[0].ident.span())
+		}
+	}
+	
+	fn visit_block(&mut self, node: &'ast syn::Block) {
+		self.shadow(&[], |this| for stmt in &node.stmts { this.visit_stmt(stmt) })
+	}
+	
+	fn visit_local(&mut self, node: &'ast syn::Local) {
+		for attr in &node.attrs { self.visit_attribute(attr) }
+		if let Some(init) = &node.init { self.visit_local_init(init) }
+		self.shadowed |= self.binds(&node.pat) // until the end of the block
+	}
+	
+	fn visit_expr_closure(&mut self, node: &'ast syn::ExprClosure) {
+		let inputs: Vec<_> = node.inputs.iter().collect();
+		self.shadow(&inputs, |this| this.visit_expr(&node.body))
+	}
+	
+	fn visit_arm(&mut self, node: &'ast syn::Arm) {
+		self.shadow(&[&node.pat], |this| {
+			if let Some((_, guard)) = &node.guard { this.visit_expr(guard) }
+			this.visit_expr(&node.body)
+		})
+	}
+	
+	fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+		self.visit_expr(&node.expr);
+		self.shadow(&[&node.pat], |this| this.visit_block(&node.body))
+	}
+	
+	fn visit_expr_if(&mut self, node: &'ast syn::ExprIf) {
+		let syn::Expr::Let(let_) = &*node.cond else { return syn::visit::visit_expr_if(self, node) };
+		self.visit_expr(&let_.expr);
+		self.shadow(&[&let_.pat], |this| this.visit_block(&node.then_branch));
+		if let Some((_, else_)) = &node.else_branch { self.visit_expr(else_) }
+	}
+	
+	fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+		let syn::Expr::Let(let_) = &*node.cond else { return syn::visit::visit_expr_while(self, node) };
+		self.visit_expr(&let_.expr);
+		self.shadow(&[&let_.pat], |this| this.visit_block(&node.body))
+	}
+	
+	fn visit_lit(&mut self, node: &'ast syn::Lit) { self.literal(node.to_token_stream().to_string(), node.span()) }
+	
+	fn visit_macro(&mut self, node: &'ast syn::Macro) { self.tokens(node.tokens.clone()) }
+	
+	fn visit_expr(&mut self, node: &'ast syn::Expr) {
+		if let syn::Expr::Verbatim(stream) = node { self.trees(stream.clone()) } else { syn::visit::visit_expr(self, node) }
+	}
+	
+	fn visit_item(&mut self, _: &'ast syn::Item) { } // cannot refer to variables
+	fn visit_pat(&mut self, _: &'ast syn::Pat) { }
+	fn visit_type(&mut self, _: &'ast syn::Type) { }
+}
+
 fn bindings_error(stream: &mut TokenStream2, spans: Vec<Span>) {
 	for span in spans { stream.extend(syn::Error::new(span, BINDINGS_ERROR).to_compile_error()) }
 }
@@ -206,6 +467,26 @@ fn extend_attributes(attrs: &mut Vec<syn::Attribute>, pattrs: &[syn::Attribute])
 	attrs.extend(current);
 }
 
+fn target_attributes(
+	attrs: Vec<syn::Attribute>
+) -> syn::Result<(Vec<syn::Attribute>, Option<Vec<syn::Attribute>>)> {
+	let (mut stmt, mut field, mut targeted) = (vec![], vec![], false);
+	
+	for attr in attrs {
+		let target = if attr.path().is_ident("field") { &mut field }
+			else if attr.path().is_ident("stmt") { &mut stmt }
+			else { field.push(attr.clone()); stmt.push(attr); continue };
+		
+		targeted = true;
+		let metas = attr.parse_args_with(Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)?;
+		
+		target.extend(metas.into_iter().map(|meta| syn::Attribute {
+			pound_token: attr.pound_token, style: attr.style, bracket_token: attr.bracket_token, meta
+		}))
+	}
+	Ok((stmt, targeted.then_some(field)))
+}
+
 fn parse_unterminated<T, P>(input: syn::parse::ParseStream) -> syn::Result<Punctuated<T, P>>
 where T: syn::parse::Parse, P: syn::parse::Parse {
 	let mut punctuated = Punctuated::new();
@@ -218,13 +499,78 @@ where T: syn::parse::Parse, P: syn::parse::Parse {
 	Ok(punctuated)
 }
 
+const FIELD_ERROR: &str = "`#[field(...)]` only applies to items exported as struct fields";
+
 const BINDINGS_ERROR: &str = "bindings must be consumed with the `bindings!` placeholder macro";
 
-struct ConstrError(&'static str);
+fn distance(a: &str, b: &str) -> usize {
+	let mut row: Vec<_> = (0..=b.chars().count()).collect();
+	
+	for (i, a) in a.chars().enumerate() {
+		let mut diagonal = std::mem::replace(&mut row[0], i + 1);
+		
+		for (j, b) in b.chars().enumerate() {
+			let substitution = diagonal + usize::from(a != b);
+			diagonal = row[j + 1];
+			row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+		}
+	}
+	row[row.len() - 1]
+}
+
+fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+	candidates.iter()
+		.map(|candidate| (distance(name, candidate), *candidate))
+		.filter(|(distance, candidate)| *distance > 0 && *distance <= candidate.len().max(3) / 3)
+		.min_by_key(|(distance, _)| *distance)
+		.map(|(_, candidate)| candidate)
+}
+
+/// What cannot be done in the initial content of an item, and how the item is expanded (see `Construction::within`).
+struct ConstrError(&'static str, &'static str);
 
 impl std::fmt::Display for ConstrError {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		f.write_str(self.0)?;
-		f.write_str(" in the initial content of an item whose definition should be expanded in a builder pattern or a struct literal")
+		write!(f, "{} in the initial content of {}", self.0, self.1)
 	}
 }
+
+#[proc_macro_derive(Observable, attributes(observable))]
+/// Generates for each field a setter (`set_field`), an in-place modifier (`update_field`) and a
+/// `field_changed()` method, which tells whether it was set or modified since `take_changes()`,
+/// along with a `FIELD_CHANGED` constant with its bit in the changes (all with the field visibility).
+///
+/// The changes are stored as bits in the field marked with `#[observable(changes)]`, which must
+/// be a `u64` (fields marked with `#[observable(skip)]` are not observed). By convention, bindings
+/// are conditioned on `field_changed()` so that refreshing the view skips the clean fields.
+///
+/// ### Basic usage
+///
+/// ~~~
+/// use declarative_macros::{block as view, Observable};
+///
+/// #[derive(Default, Observable)]
+/// struct Model {
+///     count: u8,
+///     #[observable(changes)] changes: u64,
+/// }
+///
+/// let mut model = Model::default();
+///
+/// view! {
+///     String::new() mut log {
+///         'bind: if model.count_changed() { push_str: &format!("count: {}; ", model.count) }
+///         'consume mut refresh = move |model: &Model| { bindings!(); log.clone() }
+///     }
+/// }
+///
+/// model.set_count(1);
+/// assert_eq!(refresh(&model), "count: 0; count: 1; ");
+///
+/// model.take_changes(); // the bindings of clean fields are skipped:
+/// assert_eq!(refresh(&model), "count: 0; count: 1; ");
+/// ~~~
+pub fn observable(stream: TokenStream) -> TokenStream {
+	let input = syn::parse_macro_input!(stream);
+	TokenStream::from(observable::derive(input).unwrap_or_else(syn::Error::into_compile_error))
+}
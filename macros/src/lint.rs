@@ -0,0 +1,159 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use proc_macro2::{Delimiter, Group, Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::cell::RefCell;
+
+struct Warning { span: Span, lint: &'static str, message: String }
+
+thread_local! {
+	static WARNINGS: RefCell<Vec<Warning>> = const { RefCell::new(vec![]) };
+	static NAMES: RefCell<Vec<(syn::Ident, bool)>> = const { RefCell::new(vec![]) };
+}
+
+pub fn start() { WARNINGS.take(); NAMES.take(); }
+
+pub fn warn(span: Span, lint: &'static str, message: String) {
+	WARNINGS.with_borrow_mut(|warnings| warnings.push(Warning { span, lint, message }))
+}
+
+pub fn name(ident: &syn::Ident, back: bool) {
+	NAMES.with_borrow_mut(|names| names.push((ident.clone(), back)))
+}
+
+pub fn finish(output: &mut TokenStream, enabled: bool, sources: Option<(&TokenStream, &[TokenStream])>) {
+	let names = NAMES.take();
+	if !enabled && !cfg!(feature = "lint") { return WARNINGS.set(vec![]) }
+	
+	if let Some((outside, views)) = sources {
+		let bodies = bodies(outside);
+		
+		for (name, back) in names {
+			let count = |streams: &[TokenStream]| streams.iter().map(|stream| crate::references(stream, &name).len()).sum::<usize>();
+			if count(&bodies) > 0 || count(views) > 1 { continue } // the view also has the declaration
+			
+			let message = if back {
+				format!("this 'back result is named `{name}` but never referenced (remove the name)")
+			} else {
+				format!("the item `{name}` is named but never referenced \
+					(remove the name to let the view generate one)")
+			};
+			warn(name.span(), if back { "unused_back" } else { "unused_item" }, message)
+		}
+	}
+	
+	for Warning { span, lint, message } in WARNINGS.take() {
+		let (name, usage) = (
+			syn::Ident::new(lint, Span::mixed_site()),
+			syn::Ident::new(lint, Span::mixed_site().located_at(span)),
+		);
+		output.extend(quote! {
+			const _: () = {
+				#[deprecated(note = #message)]
+				#[allow(non_upper_case_globals)]
+				const #name: () = ();
+				#usage
+			};
+		})
+	}
+}
+
+/// Removes the `view!` macros from the code of an item, returning them apart.
+pub fn split(stream: TokenStream, views: &mut Vec<TokenStream>) -> TokenStream {
+	let mut trees: Vec<_> = stream.into_iter().collect();
+	let mut index = 0;
+	
+	while index < trees.len() {
+		if let [TokenTree::Ident(ident), TokenTree::Punct(bang), TokenTree::Group(group), ..] = &trees[index..] {
+			if ident == "view" && bang.as_char() == '!' && !matches!(
+				index.checked_sub(1).map(|index| &trees[index]), Some(TokenTree::Punct(punct)) if punct.as_char() == ':'
+			) {
+				views.push(group.stream());
+				trees.drain(index..index + 3);
+				continue
+			}
+		}
+		if let TokenTree::Group(group) = &trees[index] {
+			let mut new = Group::new(group.delimiter(), split(group.stream(), views));
+			new.set_span(group.span());
+			trees[index] = TokenTree::Group(new)
+		}
+		index += 1
+	}
+	trees.into_iter().collect()
+}
+
+fn bodies(stream: &TokenStream) -> Vec<TokenStream> {
+	struct Bodies(Vec<TokenStream>);
+	
+	impl syn::visit::Visit<'_> for Bodies {
+		fn visit_block(&mut self, node: &syn::Block) { self.0.push(node.to_token_stream()) }
+		fn visit_expr(&mut self, node: &syn::Expr) { self.0.push(node.to_token_stream()) }
+	}
+	let Ok(item) = syn::parse2::<syn::Item>(stream.clone()) else { return vec![stream.clone()] };
+	let mut bodies = Bodies(vec![]);
+	syn::visit::Visit::visit_item(&mut bodies, &item);
+	bodies.0
+}
+
+pub fn method(path: &crate::Path) -> Option<&syn::Ident> {
+	let crate::Path::Type(ty) = path else { return None };
+	ty.path.get_ident()
+}
+
+/// Whether a method is expected to be called more than once on a builder (e.g. to add children).
+pub fn is_additive(name: &syn::Ident) -> bool {
+	let name = name.to_string();
+	["add", "append", "push", "insert", "extend"].iter().any(|prefix| name.starts_with(prefix))
+		|| ["attribute", "child", "class"].contains(&&*name)
+}
+
+pub fn is_setter(name: &syn::Ident) -> bool { name.to_string().starts_with("set_") }
+
+pub fn called(settings: &TokenStream, assignee: &[syn::Ident], name: &syn::Ident) -> bool {
+	let trees: Vec<_> = settings.clone().into_iter().collect();
+	let path: Vec<_> = assignee.iter().chain([name]).collect();
+	
+	trees.windows(path.len() * 2).enumerate().any(|(index, window)| {
+		!matches!(index.checked_sub(1).map(|index| &trees[index]), Some(TokenTree::Punct(punct)) if punct.as_char() == '.')
+			&& window.chunks(2).zip(&path).enumerate().all(|(index, (pair, ident))| match pair {
+				[TokenTree::Ident(a), TokenTree::Punct(dot)] if index + 1 < path.len() => a == *ident && dot.as_char() == '.',
+				[TokenTree::Ident(a), TokenTree::Group(args)] if index + 1 == path.len() =>
+					a == *ident && args.delimiter() == Delimiter::Parenthesis,
+				_ => false
+			})
+	})
+}
+
+pub fn chained(right: &TokenStream, name: &syn::Ident) -> bool {
+	let mut dot = false;
+	
+	for tree in right.clone() {
+		if let (true, TokenTree::Ident(ident)) = (dot, &tree) {
+			if ident == name { return true }
+		}
+		dot = matches!(tree, TokenTree::Punct(ref punct) if punct.as_char() == '.');
+	}
+	false
+}
+
+pub fn references(stream: TokenStream) -> bool {
+	let mut trees = stream.into_iter().peekable();
+	
+	while let Some(tree) = trees.next() {
+		if match tree {
+			TokenTree::Group(group) => references(group.stream()),
+			TokenTree::Ident(ident) => ident != "true" && ident != "false" && !matches!(
+				trees.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '!' && punct.spacing() == Spacing::Alone
+			),
+			TokenTree::Literal(literal) => literal.to_string().split('{').skip(1)
+				.any(|part| part.starts_with(|char: char| char.is_alphabetic() || char == '_')),
+			TokenTree::Punct(_) => false,
+		} { return true }
+	}
+	false
+}
@@ -0,0 +1,93 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+
+enum Field { Observed, Changes, Skipped }
+
+fn parse_field(attrs: &[syn::Attribute]) -> syn::Result<Field> {
+	let mut field = Field::Observed;
+	
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("observable")) {
+		attr.parse_nested_meta(|meta| {
+			if !matches!(field, Field::Observed) { Err(meta.error("the field was already marked"))? }
+			
+			if meta.path.is_ident("changes") { field = Field::Changes; Ok(()) }
+			else if meta.path.is_ident("skip") { field = Field::Skipped; Ok(()) }
+			else { Err(meta.error("expected `changes` or `skip`")) }
+		})?
+	}
+	Ok(field)
+}
+
+pub fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+	let syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(named), .. }) = &input.data else {
+		return Err(syn::Error::new_spanned(&input.ident, "`Observable` can only be derived for structs with named fields"))
+	};
+	
+	let syn::DeriveInput { vis, ident, generics, .. } = &input;
+	let (impl_gens, ty_gens, where_clause) = generics.split_for_impl();
+	let (mut changes, mut observed) = (None, vec![]);
+	
+	for field in &named.named {
+		let name = field.ident.as_ref().unwrap();
+		
+		match parse_field(&field.attrs)? {
+			Field::Observed => observed.push((name, &field.ty, &field.vis)),
+			Field::Changes if changes.is_some() => Err(syn::Error::new_spanned(name, "only one field can store the changes"))?,
+			Field::Changes => changes = Some(name),
+			Field::Skipped => (),
+		}
+	}
+	
+	let Some(changes) = changes else {
+		return Err(syn::Error::new_spanned(ident, "`Observable` needs a field to store which fields changed \
+			(add `#[observable(changes)] changes: u64` to the struct)"))
+	};
+	if let Some((name, ..)) = observed.get(64) {
+		Err(syn::Error::new_spanned(name, "cannot observe more than 64 fields (mark the rest with `#[observable(skip)]`)"))?
+	}
+	
+	let methods = observed.iter().enumerate().map(|(index, (name, ty, vis))| { // with the visibility of the field
+		let (set, update, changed) = (format_ident!("set_{name}"), format_ident!("update_{name}"), format_ident!("{name}_changed"));
+		let constant = format_ident!("{}_CHANGED", name.unraw().to_string().to_uppercase(), span = name.span());
+		let bit = quote![Self::#constant];
+		
+		quote! {
+			#[doc = concat!("The bit of `", stringify!(#name), "` in the value returned by `take_changes()`.")]
+			#vis const #constant: u64 = 1 << #index;
+			
+			#[doc = concat!("Sets `", stringify!(#name), "` and marks it as changed.")]
+			#vis fn #set(&mut self, value: #ty) { self.#name = value; self.#changes |= #bit }
+			
+			#[doc = concat!("Modifies `", stringify!(#name), "` in place and marks it as changed.")]
+			#vis fn #update(&mut self, update: impl ::core::ops::FnOnce(&mut #ty)) {
+				update(&mut self.#name); self.#changes |= #bit
+			}
+			
+			#[doc = concat!("Returns `true` if `", stringify!(#name), "` changed since the last `take_changes()`.")]
+			#vis fn #changed(&self) -> bool { self.#changes & #bit != 0 }
+		}
+	});
+	
+	Ok(quote! {
+		#[allow(dead_code)] // not every method is used
+		impl #impl_gens #ident #ty_gens #where_clause {
+			#(#methods)*
+			
+			/// Returns `true` if any field changed since the last `take_changes()`.
+			#vis fn changed(&self) -> bool { self.#changes != 0 }
+			
+			/// Marks every field as changed (e.g. to refresh everything the first time).
+			#vis fn change_all(&mut self) { self.#changes = !0 }
+			
+			/// Marks every field as unchanged and returns the previous bits.
+			#vis fn take_changes(&mut self) -> u64 { ::core::mem::take(&mut self.#changes) }
+		}
+	})
+}
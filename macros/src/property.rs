@@ -9,35 +9,85 @@ use quote::{quote, quote_spanned};
 use syn::{punctuated::Punctuated, visit_mut::VisitMut};
 use crate::{content, item, Assignee, Attributes, ConstrError, Construction};
 
+#[derive(Copy, Clone)]
 enum Mode { Field, Method, FnField, Auto }
 
 pub struct Property {
 	 attrs: Vec<syn::Attribute>,
 	  path: crate::Path,
+	  bang: Option<syn::Token![!]>,
 	by_ref: Option<syn::Token![&]>,
 	  mut_: Option<syn::Token![mut]>,
 	  mode: (Mode, Span),
 	  args: Punctuated<syn::Expr, syn::Token![,]>,
 	 items: Vec<item::Item>,
+	  with: Option<Box<With>>,
 	  back: Option<Box<item::Back>>,
 }
 
+pub struct With {
+	token: syn::Lifetime,
+	move_: Option<syn::Token![move]>,
+	 mut_: Option<syn::Token![mut]>,
+	 name: syn::Ident,
+	brace: syn::token::Brace,
+	 body: Vec<content::Content>,
+}
+
+fn peek_with(input: syn::parse::ParseStream) -> bool {
+	input.fork().parse::<syn::Lifetime>().map(|keyword| keyword.ident == "with").unwrap_or(false)
+}
+
+fn parse_with(input: syn::parse::ParseStream) -> syn::Result<Option<Box<With>>> {
+	if !peek_with(input) { return Ok(None) }
+	
+	let (token, move_, mut_, name) = (input.parse()?, input.parse()?, input.parse()?, input.parse()?);
+	let (brace, body) = content::parse_vec(input)?;
+	Ok(Some(Box::new(With { token, move_, mut_, name, brace, body })))
+}
+
+fn expand_with(With { token, move_, mut_, name, brace, body }: With) -> syn::Expr {
+	let body = content::scope(body, &[], Assignee::Ident(None, &name));
+	let mut body = Group::new(Delimiter::Brace, body);
+	body.set_span(brace.span.join());
+	syn::Expr::Verbatim(quote_spanned![token.span() => #move_ |#mut_ #name| #body])
+}
+
+impl Property {
+	pub fn chains(&self) -> bool {
+		self.attrs.is_empty() && (!self.path.is_long() || self.bang.is_some()) && self.back.is_none()
+			&& matches!(self.mode.0, Mode::Method | Mode::Auto)
+	}
+	
+	pub fn is_constant(&self) -> bool {
+		let Property { args, items, with, back, .. } = self;
+		!args.is_empty() && items.is_empty() && with.is_none() && back.is_none()
+			&& !crate::lint::references(quote![#args])
+	}
+}
+
 pub fn parse(input: syn::parse::ParseStream, attrs: Vec<syn::Attribute>) -> syn::Result<Box<Property>> {
-	let rest = |callable| {
-		let args = if callable { crate::parse_unterminated(input)? }
-			else { Punctuated::from_iter([input.parse::<syn::Expr>()?]) };
+	let rest = |callable: bool| {
+		let args = if !callable { Punctuated::from_iter([input.parse::<syn::Expr>()?]) }
+			else if peek_with(input) { Punctuated::new() } else { crate::parse_unterminated(input)? };
 		
+		let with = if callable { parse_with(input)? } else { None };
 		let mut items = vec![];
 		while input.peek(syn::Token![@]) { items.push(item::parse(input, None)?) }
 		
 		let _ = items.is_empty().then(|| input.parse::<syn::Token![;]>());
 		let back = if callable { item::parse_back(input)? } else { None };
-		Ok::<_, syn::Error>((args, items, back))
+		Ok::<_, syn::Error>((args, items, with, back))
 	};
 	
 	let path: crate::Path = input.parse()?;
+	let bang: Option<syn::Token![!]> = input.parse()?;
+	
+	if let (Some(_), crate::Path::Field { .. }) = (bang, &path) {
+		Err(syn::Error::new_spanned(&path, "expected a macro path, found a field"))?
+	}
 	
-	let (by_ref, mut_) = if path.is_long() {
+	let (by_ref, mut_) = if path.is_long() && bang.is_none() {
 		let by_ref: Option<_> = input.parse()?;
 		(by_ref, by_ref.and_then(|_| input.parse().ok()))
 	} else { (None, None) };
@@ -45,29 +95,36 @@ pub fn parse(input: syn::parse::ParseStream, attrs: Vec<syn::Attribute>) -> syn:
 	syn::custom_punctuation!(ColonEq, :=);
 	syn::custom_punctuation!(SemiSemi, ;;);
 	
-	let (mode, (args, items, back)) = if let Ok(eq) = input.parse::<syn::Token![=]>() {
+	let (mode, (args, items, with, back)) = if let Ok(eq) = input.parse::<syn::Token![=]>() {
 		((Mode::Field, eq.span), rest(false)?)
 	} else if let Ok(colon_eq) = input.parse::<ColonEq>() {
 		((Mode::FnField, colon_eq.spans[1]), rest(true)?)
 	} else if let Ok(colon) = input.parse::<syn::Token![:]>() {
 		((Mode::Method, colon.span), rest(true)?)
 	} else if let Ok(semis) = input.parse::<SemiSemi>() {
-		((Mode::FnField, semis.spans[1]), (Punctuated::new(), vec![], item::parse_back(input)?))
+		((Mode::FnField, semis.spans[1]), (Punctuated::new(), vec![], None, item::parse_back(input)?))
 	} else if let Ok(semi) = input.parse::<syn::Token![;]>() {
-		((Mode::Method, semi.span), (Punctuated::new(), vec![], item::parse_back(input)?))
+		((Mode::Method, semi.span), (Punctuated::new(), vec![], None, item::parse_back(input)?))
 	} else { ((Mode::Auto, Span::call_site()), Default::default()) };
 	
-	Ok(Box::new(Property { attrs, path, by_ref, mut_, mode, args, items, back }))
+	Ok(Box::new(Property { attrs, path, bang, by_ref, mut_, mode, args, items, with, back }))
 }
 
 fn check_property(
-	attrs: Option<&[syn::Attribute]>, path: &crate::Path, mode: (Mode, Span), back: Option<Box<item::Back>>
+	 attrs: Option<&Vec<syn::Attribute>>,
+	  path: &crate::Path,
+	  bang: Option<&syn::Token![!]>,
+	  mode: (Mode, Span),
+	  back: Option<&item::Back>,
+	within: &'static str,
 ) -> syn::Result<()> {
-	if path.is_long() { Err(syn::Error::new_spanned(path, ConstrError("cannot use long path")))? }
+	if let Some(bang) = bang {
+		if attrs.is_none() { Err(syn::Error::new(bang.span, ConstrError("cannot call macros", within)))? }
+	} else if path.is_long() { Err(syn::Error::new_spanned(path, ConstrError("cannot use long path", within)))? }
 	
 	if let Some(attrs) = attrs {
 		if !attrs.is_empty() {
-			Err(syn::Error::new_spanned(quote![#(#attrs)*], ConstrError("cannot use attributes")))?
+			Err(syn::Error::new_spanned(quote![#(#attrs)*], ConstrError("cannot use attributes", within)))?
 		}
 	} else if match path {
 		crate::Path::Type(path) => path.path.get_ident().is_none(),
@@ -75,16 +132,16 @@ fn check_property(
 	} { Err(syn::Error::new_spanned(path, "cannot give generics to struct fields"))? }
 	
 	if let Some(back) = back {
-		Err(syn::Error::new(back.token.span(), ConstrError("cannot use 'back")))?
+		Err(syn::Error::new(back.token.span(), ConstrError("cannot use 'back", within)))?
 	}
 	if let Mode::Field | Mode::FnField = mode.0 {
-		Err(syn::Error::new(mode.1, ConstrError("can only use colon or single semicolon")))
+		Err(syn::Error::new(mode.1, ConstrError("can only use colon or single semicolon", within)))
 	} else { Ok(()) }
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn expand(
-	Property { mut attrs, path, by_ref, mut_, mode, mut args, items, back }: Property,
+	Property { mut attrs, path, bang, by_ref, mut_, mode, mut args, items, with, back }: Property,
 	 objects: &mut TokenStream,
 	 constrs: &mut Vec<Construction>,
 	settings: &mut TokenStream,
@@ -125,6 +182,8 @@ pub fn expand(
 		item::expand(item, objects, constrs, settings, bindings, fields, pattrs)
 	}
 	
+	if let Some(with) = with { args.push(expand_with(*with)) }
+	
 	let (right, back) = 'tuple: {
 		if no_assignee {
 			let span = match mode.0 {
@@ -141,23 +200,49 @@ pub fn expand(
 				objects.extend(syn::Error::new(
 					by_ref.span, "cannot use `&` with an extra underscore"
 				).into_compile_error())
-			} else { break 'tuple (quote_spanned![span => #path(#args)], back) }
+			} else { break 'tuple (quote_spanned![span => #path #bang (#args)], back) }
+		}
+		
+		let mut constr = constr;
+		
+		if let Some(index) = constr {
+			let (literal, within) = (matches!(constrs[index], Construction::StructLiteral { .. }), constrs[index].within());
+			let mut error = check_property(
+				(!literal).then_some(&attrs), &path, bang.as_ref(), mode, back.as_deref(), within
+			).err();
+			
+			let builder = matches!(constrs[index], Construction::BuilderPattern {
+				origin: crate::Origin::Braces | crate::Origin::Call, ..
+			}); // called after building instead
+			if error.is_some() && builder && !crate::strict() { error = None; constr = None }
+			
+			if literal && args.len() > 1 {
+				let multiple = syn::Error::new_spanned(&args, ConstrError("cannot give multiple arguments", within));
+				if let Some(error) = &mut error { error.combine(multiple) } else { error = Some(multiple) }
+			}
+			if let Some(error) = error { // without the property, which would cause more errors
+				return objects.extend(error.into_compile_error())
+			}
 		}
 		
 		match constr.map(|index| &mut constrs[index]) {
 			Some(Construction::BuilderPattern { right, span, .. }) => {
-				if let Err(error) = check_property(Some(&attrs), &path, mode, back)
-					{ objects.extend(error.into_compile_error()) }
+				if let Some(bang) = bang {
+					let builder = if syn::parse2::<syn::Expr>(right.clone()).is_err() { // `Type => methods`
+						quote_spanned![*span => construct!(~ #right)]
+					} else { std::mem::take(right) };
+					
+					let args = args.iter();
+					return *right = quote_spanned![*span => #path #bang (#builder #(, #args)*)]
+				}
+				if let Some(name) = crate::lint::method(&path)
+					.filter(|name| !crate::lint::is_additive(name) && crate::lint::chained(right, name))
+				{
+					crate::lint::warn(name.span(), "duplicate_property", format!("`{name}` is already set in the builder of this item"))
+				}
 				return right.extend(quote_spanned![*span => .#path(#args)])
 			}
 			Some(Construction::StructLiteral { fields, span, .. }) => {
-				if let Err(error) = check_property(None, &path, mode, back) {
-					objects.extend(error.into_compile_error())
-				}
-				if args.len() > 1 {
-					let error = "cannot give multiple arguments";
-					objects.extend(syn::Error::new_spanned(&args, error).into_compile_error())
-				}
 				let args = args.iter();
 				return fields.extend(quote_spanned![*span => #path #(: #args)*,])
 			}
@@ -165,6 +250,16 @@ pub fn expand(
 		}
 		
 		let assignee = assignee.spanned_to(mode.1);
+		
+		if let Some(bang) = bang {
+			if let Mode::Field | Mode::FnField = mode.0 {
+				let error = syn::Error::new(mode.1, "use `:` or `;` instead of `=`, `:=` or `;;` after a macro");
+				objects.extend(error.into_compile_error())
+			}
+			let args = args.iter();
+			break 'tuple (quote_spanned![mode.1 => #path #bang (#(#assignee).* #(, #args)*)], back)
+		}
+		
 		match mode.0 {
 			Mode::Field => {
 				let pattrs = pattrs.get(fields);
@@ -173,6 +268,13 @@ pub fn expand(
 			Mode::Method => if path.is_long() {
 				(quote_spanned![mode.1 => #path(#by_ref #mut_ #(#assignee).*, #args)], back)
 			} else {
+				let assignee: Vec<_> = assignee.collect();
+				
+				if let Some(name) = crate::lint::method(&path).filter(|name| crate::lint::is_setter(name) && attrs.is_empty()) {
+					if crate::lint::called(settings, &assignee, name) { crate::lint::warn(
+						name.span(), "duplicate_property", format!("`{name}` is already called on this item")
+					) }
+				}
 				let mut args = Group::new(Delimiter::Parenthesis, quote![#args]);
 				args.set_span(path.span());
 				(quote_spanned![mode.1 => #(#assignee.)* #path #args], back)
@@ -0,0 +1,152 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+
+enum Prop { Required(syn::Ident), Default(TokenStream) }
+
+fn parse_default(attrs: &[syn::Attribute]) -> syn::Result<Option<TokenStream>> {
+	let mut default = None;
+	
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("prop")) {
+		attr.parse_nested_meta(|meta| {
+			if default.is_some() { Err(meta.error("the default value was already given"))? }
+			
+			if meta.path.is_ident("default") {
+				default = Some(if meta.input.peek(syn::Token![=]) {
+					let expr: syn::Expr = meta.value()?.parse()?; quote![#expr]
+				} else { quote![::core::default::Default::default()] });
+				Ok(())
+			} else if meta.path.is_ident("optional") {
+				default = Some(quote![::core::default::Default::default()]); Ok(())
+			} else { Err(meta.error("expected `default`, `default = ...` or `optional`")) }
+		})?
+	}
+	Ok(default)
+}
+
+pub fn derive(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+	let syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(named), .. }) = &input.data else {
+		return Err(syn::Error::new_spanned(&input.ident, "`Props` can only be derived for structs with named fields"))
+	};
+	
+	let syn::DeriveInput { vis, ident, generics, .. } = &input;
+	let props = format_ident!("__{ident}Props"); // not `Props`, which could be the name of the struct
+	let (impl_gens, ty_gens, where_clause) = generics.split_for_impl();
+	let args = generics.params.iter().map(|param| match param {
+		syn::GenericParam::Lifetime(param) => { let lifetime = &param.lifetime; quote![#lifetime] }
+		syn::GenericParam::Type(param) => { let ident = &param.ident; quote![#ident] }
+		syn::GenericParam::Const(param) => { let ident = &param.ident; quote![#ident] }
+	}).collect::<Vec<_>>();
+	
+	let params = generics.params.iter().cloned().map(|mut param| {
+		if let syn::GenericParam::Type(param) = &mut param { (param.eq_token, param.default) = (None, None) }
+		if let syn::GenericParam::Const(param) = &mut param { (param.eq_token, param.default) = (None, None) }
+		param
+	}).collect::<Vec<_>>();
+	let (mut names, mut types, mut defaults) = (vec![], vec![], vec![]);
+	
+	for field in &named.named {
+		let name = field.ident.as_ref().unwrap();
+		
+		defaults.push(match parse_default(&field.attrs)? {
+			Some(value) => Prop::Default(value),
+			None => Prop::Required(format_ident!("__{name}")),
+		});
+		names.push(name); types.push(&field.ty)
+	}
+	
+	// a type parameter per required field: `()` until it is set, `(T,)` after that
+	let states = defaults.iter().enumerate().filter_map(|(index, default)| match default {
+		Prop::Required(_) => Some(format_ident!("__R{index}")),
+		Prop::Default(_) => None,
+	}).collect::<Vec<_>>();
+	
+	let field_tys = types.iter().zip(&defaults).enumerate().map(|(index, (ty, default))| match default {
+		Prop::Required(_) => { let state = format_ident!("__R{index}"); quote![#state] }
+		Prop::Default(_) => quote![::core::option::Option<#ty>],
+	});
+	
+	let unset = defaults.iter().map(|default| match default {
+		Prop::Required(_) => quote![()],
+		Prop::Default(_) => quote![::core::option::Option::None],
+	}).collect::<Vec<_>>();
+	let unset_states = states.iter().map(|_| quote![()]);
+	
+	let setters = names.iter().zip(&types).zip(&defaults).enumerate().map(|(index, ((name, ty), default))| {
+		match default {
+			Prop::Default(_) => quote! {
+				#vis fn #name(mut self, #name: #ty) -> Self {
+					self.#name = ::core::option::Option::Some(#name); self
+				}
+			},
+			Prop::Required(_) => {
+				let result = states.iter().map(|state| if *state == format_ident!("__R{index}")
+					{ quote![(#ty,)] } else { quote![#state] });
+				let rest = names.iter().map(|other| if other == name
+					{ quote![#name: (#name,)] } else { quote![#other: self.#other] });
+				quote! {
+					#vis fn #name(self, #name: #ty) -> #props<#(#args,)* #(#result),*> {
+						#props { #(#rest,)* __marker: ::core::marker::PhantomData }
+					}
+				}
+			}
+		}
+	});
+	
+	let traits = names.iter().zip(&defaults).filter_map(|(name, default)| {
+		let Prop::Required(trait_) = default else { return None };
+		let message = format!("missing required field `{name}` for `{ident}`");
+		let label = format!("`{name}` must be given before `..`");
+		let note = format!("`{name}` is optional if it is given a default (e.g. `#[prop(default = ...)]`)");
+		
+		Some(quote_spanned! { name.span() =>
+			#[allow(non_camel_case_types)]
+			#[diagnostic::on_unimplemented(message = #message, label = #label, note = #note)]
+			pub trait #trait_<T> { fn take(self) -> T; }
+			impl<T> #trait_<T> for (T,) { fn take(self) -> T { self.0 } }
+		})
+	}).collect::<Vec<_>>();
+	
+	let bounds = states.iter().zip(defaults.iter().zip(&types).filter_map(|(default, ty)| match default {
+		Prop::Required(trait_) => Some(quote![#trait_<#ty>]),
+		Prop::Default(_) => None,
+	})).map(|(state, bound)| quote![#state: #bound]);
+	
+	let build = names.iter().zip(&defaults).map(|(name, default)| match default {
+		Prop::Required(trait_) => quote![#name: #trait_::take(self.#name)],
+		Prop::Default(value) => quote![#name: self.#name.unwrap_or_else(|| #value)],
+	});
+	
+	Ok(quote! {
+		const _: () = {
+			#(#traits)*
+			
+			#[doc(hidden)]
+			pub struct #props<#(#params,)* #(#states),*> #where_clause {
+				#(#names: #field_tys,)*
+				__marker: ::core::marker::PhantomData<fn() -> #ident #ty_gens>,
+			}
+			
+			impl #impl_gens #ident #ty_gens #where_clause {
+				/// Starts the properties of this struct, whose required fields
+				/// must be set before calling `build()`.
+				#vis fn props() -> #props<#(#args,)* #(#unset_states),*> {
+					#props { #(#names: #unset,)* __marker: ::core::marker::PhantomData }
+				}
+			}
+			
+			impl<#(#params,)* #(#states),*> #props<#(#args,)* #(#states),*> #where_clause {
+				#(#setters)*
+				
+				#vis fn build(self) -> #ident #ty_gens where #(#bounds),* {
+					#ident { #(#build),* }
+				}
+			}
+		};
+	})
+}
@@ -4,12 +4,12 @@
  * SPDX-License-Identifier: (Apache-2.0 or MIT)
  */
 
-use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream};
-use quote::{TokenStreamExt, quote_spanned};
-use syn::{punctuated::Punctuated, visit_mut::VisitMut};
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenStream, TokenTree};
+use quote::{TokenStreamExt, quote, quote_spanned};
+use syn::{ext::IdentExt, parse::discouraged::Speculative, punctuated::Punctuated, visit_mut::VisitMut};
 use crate::{item, Attributes, Bindings, Range};
 
-pub enum Root { Struct(syn::ItemStruct), Item(item::Item) }
+pub enum Root { Struct(syn::ItemStruct), Item(item::Item), Error(syn::Error) }
 
 pub struct Roots(Vec<Root>);
 
@@ -18,23 +18,220 @@ impl syn::parse::Parse for Roots {
 		let mut props = vec![];
 		
 		while !input.is_empty() {
-			let attrs = input.call(syn::Attribute::parse_outer)?;
+			let fork = input.fork();
 			
-			if input.peek(syn::Token![pub]) || input.peek(syn::Token![struct]) {
-				let mut item = input.parse::<syn::ItemStruct>()?;
-				
-				let syn::Fields::Named(_) = item.fields else { Err(
-					syn::Error::new_spanned(item, "must be a struct with braces (named fields)")
-				)? };
-				
-				item.attrs = attrs; props.push(Root::Struct(item))
-			} else { props.push(Root::Item(item::parse(input, Some(attrs))?)) }
+			match parse_root(&fork) {
+				Ok(root) => { input.advance_to(&fork); props.push(root) }
+				Err(error) => {
+					props.push(Root::Error(error));
+					
+					while let Ok(tree) = input.parse::<TokenTree>() {
+						if let TokenTree::Group(group) = tree {
+							if group.delimiter() == Delimiter::Brace { break }
+						}
+					}
+					if input.parse::<Option<syn::Token![!]>>().is_ok_and(|bang| bang.is_none()) {
+						let _ = input.parse::<Option<syn::Token![?]>>();
+					}
+				}
+			}
 		}
 		
 		Ok(Self(props))
 	}
 }
 
+fn parse_root(input: syn::parse::ParseStream) -> syn::Result<Root> {
+	let attrs = input.call(syn::Attribute::parse_outer)?;
+	
+	if input.peek(syn::Token![pub]) || input.peek(syn::Token![struct]) {
+		let mut item = input.parse::<syn::ItemStruct>()?;
+		
+		let syn::Fields::Named(_) = item.fields else { Err(
+			syn::Error::new_spanned(item, "must be a struct with braces (named fields)")
+		)? };
+		
+		item.attrs = attrs; Ok(Root::Struct(item))
+	} else { Ok(Root::Item(item::parse(input, Some(attrs))?)) }
+}
+
+pub struct Block {
+	    pub lint: bool,
+	pub document: bool,
+	  pub strict: bool,
+	   pub krate: Option<syn::Path>,
+	  pub yields: Option<syn::Expr>,
+	   pub roots: Roots,
+}
+
+fn parse_crate(input: syn::parse::ParseStream) -> syn::Result<syn::Path> {
+	input.parse::<syn::Token![crate]>()?; input.parse::<syn::Token![=]>()?;
+	input.call(syn::Path::parse_mod_style)
+}
+
+fn parse_order(input: syn::parse::ParseStream) -> syn::Result<bool> {
+	input.parse::<syn::Ident>()?; input.parse::<syn::Token![=]>()?;
+	let value = input.parse::<syn::Ident>()?;
+	
+	match value.to_string().as_str() {
+		"document" => Ok(true),
+		 "reverse" => Ok(false),
+		_ => Err(syn::Error::new_spanned(value, "expected `document` or `reverse`"))
+	}
+}
+
+impl syn::parse::Parse for Block {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let (mut lint, mut order, mut strict, mut krate) = (false, None, false, None);
+		
+		while let Ok(key) = input.fork().call(syn::Ident::parse_any) {
+			let duplicate = |done: bool| if done {
+				Err(syn::Error::new_spanned(&key, format!("`{key}` was already configured")))
+			} else { Ok(()) };
+			
+			if key == "order" && input.peek2(syn::Token![=]) {
+				duplicate(order.is_some())?; order = Some(parse_order(input)?)
+			} else if key == "lint" && (input.peek2(syn::Token![,]) || input.peek2(syn::Token![;])) {
+				duplicate(lint)?; input.parse::<syn::Ident>()?; lint = true
+			} else if key == "strict" && (input.peek2(syn::Token![,]) || input.peek2(syn::Token![;])) {
+				duplicate(strict)?; input.parse::<syn::Ident>()?; strict = true
+			} else if key == "crate" && input.peek2(syn::Token![=]) {
+				duplicate(krate.is_some())?; krate = Some(parse_crate(input)?)
+			} else { break }
+			
+			if input.parse::<Option<syn::Token![,]>>()?.is_none() { input.parse::<syn::Token![;]>()?; break }
+		}
+		
+		let document = order.unwrap_or(false);
+		
+		let Some(arrow) = input.parse::<Option<syn::Token![=>]>>()? else {
+			return Ok(Block { lint, document, strict, krate, yields: None, roots: input.parse()? })
+		};
+		
+		let fork = input.fork();
+		let expr = fork.parse::<syn::Expr>().and_then(|expr| { fork.parse::<syn::Token![;]>()?; Ok(expr) });
+		if let Ok(expr) = expr {
+			input.advance_to(&fork);
+			return Ok(Block { lint, document, strict, krate, yields: Some(expr), roots: input.parse()? })
+		}
+		
+		let roots: Roots = input.parse()?;
+		let mut items = roots.0.iter().filter_map(|root| if let Root::Item(item) = root { Some(item) } else { None });
+		
+		let (Some(item), None) = (items.next(), items.next()) else { Err(syn::Error::new(arrow.spans[0],
+			"without an expression followed by `;` (e.g. `=> (first, second);`), \
+			 `=>` can only yield the only root item of the view"))?
+		};
+		let assignee = item.as_assignee();
+		Ok(Block { lint, document, strict, krate, yields: Some(syn::Expr::Verbatim(quote::quote![#assignee])), roots })
+	}
+}
+
+pub struct Args {
+	       pub lint: bool,
+	   pub document: bool,
+	     pub strict: bool,
+	  pub component: bool,
+	      pub krate: Option<syn::Path>,
+	     pub derive: Vec<syn::Path>,
+	pub placeholder: Option<syn::Ident>,
+	  pub streaming: Streaming,
+}
+
+impl syn::parse::Parse for Args {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let (mut lint, mut order, mut strict, mut component) = (false, None, false, false);
+		let (mut krate, mut derive, mut placeholder) = (None, vec![], None);
+		let (mut strukt, mut fields) = (None::<(syn::Visibility, Option<syn::Ident>, syn::Generics)>, None);
+		
+		while keys(input)? {
+			let key = input.fork().call(syn::Ident::parse_any)?;
+			
+			let duplicate = |done: bool| if done {
+				Err(syn::Error::new_spanned(&key, format!("`{key}` was already configured")))
+			} else { Ok(()) };
+			
+			match key.to_string().as_str() {
+				"lint" => { duplicate(lint)?; input.parse::<syn::Ident>()?; lint = true }
+				"strict" => { duplicate(strict)?; input.parse::<syn::Ident>()?; strict = true }
+				"component" => { duplicate(component)?; input.parse::<syn::Ident>()?; component = true }
+				"order" => { duplicate(order.is_some())?; order = Some(parse_order(input)?) }
+				"crate" => { duplicate(krate.is_some())?; krate = Some(parse_crate(input)?) }
+				"struct" => {
+					duplicate(strukt.is_some())?;
+					input.parse::<syn::Token![struct]>()?; input.parse::<syn::Token![=]>()?;
+					strukt = Some((input.parse()?, input.parse()?, input.parse()?))
+				}
+				"fields" => {
+					duplicate(fields.is_some())?; input.parse::<syn::Ident>()?;
+					let parens; syn::parenthesized!(parens in input);
+					fields = Some(parens.parse_terminated(syn::Field::parse_named, syn::Token![,])?)
+				}
+				"derive" => {
+					duplicate(!derive.is_empty())?; input.parse::<syn::Ident>()?;
+					let parens; syn::parenthesized!(parens in input);
+					derive.extend(parens.parse_terminated(syn::Path::parse_mod_style, syn::Token![,])?)
+				}
+				_ => {
+					duplicate(placeholder.is_some())?; input.parse::<syn::Ident>()?;
+					input.parse::<syn::Token![=]>()?; placeholder = Some(input.parse()?)
+				}
+			}
+			if input.is_empty() || input.parse::<Option<syn::Token![;]>>()?.is_some() { break }
+			input.parse::<syn::Token![,]>()?;
+		}
+		
+		let streaming = if strukt.is_none() && fields.is_none() { input.parse()? } else {
+			if !input.is_empty() { Err(input.error(
+				"`struct = ...` or `fields(...)` cannot be followed by more content"
+			))? }
+			let (vis, ident, generics) = strukt.unwrap_or((syn::Visibility::Inherited, None, Default::default()));
+			Streaming::Struct { vis, ident, generics, fields: fields.unwrap_or_default() }
+		};
+		Ok(Self { lint, document: order.unwrap_or(false), strict, component, krate, derive, placeholder, streaming })
+	}
+}
+
+/// Whether the input begins with keys, which are separated by `,` and end with `;` or the input.
+fn keys(input: syn::parse::ParseStream) -> syn::Result<bool> {
+	let (fork, mut first) = (input.fork(), true);
+	
+	loop {
+		let key = fork.fork().call(syn::Ident::parse_any).ok();
+		let shaped = key.as_ref().is_some_and(|key| match key.to_string().as_str() {
+			"lint" | "strict" | "component" => fork.peek2(syn::Token![,]) || fork.peek2(syn::Token![;]) || fork.peek2(syn::parse::End),
+			"order" | "crate" | "struct" | "placeholder" => fork.peek2(syn::Token![=]),
+			"fields" | "derive" => fork.peek2(syn::token::Paren),
+			_ => false
+		});
+		if !shaped {
+			if first { return Ok(false) }
+			Err(fork.error("expected a key (view content must be separated from the keys by `;`)"))?
+		}
+		let mut angles = 0_usize; // commas can separate generics
+		
+		match fork.call(syn::Ident::parse_any)?.to_string().as_str() {
+			"lint" | "strict" | "component" => (),
+			"fields" | "derive" => { fork.parse::<proc_macro2::Group>()?; }
+			_ => while !fork.is_empty() && (angles > 0 || (!fork.peek(syn::Token![,]) && !fork.peek(syn::Token![;]))) {
+				match fork.parse::<proc_macro2::TokenTree>()? {
+					proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '<' => angles += 1,
+					proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '>' => angles = angles.saturating_sub(1),
+					_ => ()
+				}
+			}
+		}
+		if fork.is_empty() || fork.peek(syn::Token![;]) { return Ok(true) }
+		if !fork.peek(syn::Token![,]) {
+			if first { return Ok(false) }
+			Err(fork.error("expected `,` or `;` after a key"))?
+		}
+		fork.parse::<syn::Token![,]>()?;
+		if fork.is_empty() { return Ok(true) }
+		first = false
+	}
+}
+
 pub enum Streaming {
 	Roots(Roots), Struct {
 		     vis: syn::Visibility,
@@ -90,13 +287,10 @@ impl syn::parse::Parse for Streaming {
 	}
 }
 
-pub fn expand(structs: &mut Vec<syn::ItemStruct>, roots: Roots, errable: bool) -> (TokenStream, Bindings) {
+pub fn expand(structs: &mut Vec<syn::ItemStruct>, roots: Roots, inferable: bool) -> (TokenStream, Bindings) {
 	let mut objects = TokenStream::new();
 	let (mut constrs, mut settings, mut bindings) = Default::default();
-	let (mut n_fields, mut strukt, mut followed) = ([0; 3], None, true);
-	
-	if let Some(syn::Fields::Named(fields)) = structs.first()
-		.map(|strukt| &strukt.fields) { n_fields[0] = fields.named.len() }
+	let (mut n_fields, mut strukt, mut followed) = ([0; 2], None, true);
 	
 	macro_rules! check_struct(() => (
 		if !followed {
@@ -105,7 +299,7 @@ pub fn expand(structs: &mut Vec<syn::ItemStruct>, roots: Roots, errable: bool) -
 			).into_compile_error())
 		}
 		if let Some(strukt) = strukt.take() {
-			if n_fields[1] == n_fields[2] {
+			if n_fields[0] == n_fields[1] {
 				objects.extend(syn::Error::new_spanned(
 					&strukt, "this struct does not refer to any item"
 				).into_compile_error())
@@ -118,13 +312,14 @@ pub fn expand(structs: &mut Vec<syn::ItemStruct>, roots: Roots, errable: bool) -
 		Root::Struct(item) => {
 			check_struct!();
 			let syn::Fields::Named(fields) = &item.fields else { panic!() };
-			n_fields[1] = fields.named.len();
+			n_fields[0] = fields.named.len();
 			strukt = Some(item);
 			followed = false;
 		}
 		Root::Item(item) => {
 			followed = true;
 			
+			item::start_inference(strukt.is_some() || inferable); // the struct of an `impl` cannot have more generics
 			let fields = &mut if let Some(strukt) = strukt.as_mut().or(structs.first_mut()) {
 				let syn::Fields::Named(fields) = &mut strukt.fields else { panic!() };
 				Some(&mut fields.named)
@@ -134,40 +329,40 @@ pub fn expand(structs: &mut Vec<syn::ItemStruct>, roots: Roots, errable: bool) -
 				item, &mut objects, &mut constrs, &mut settings,
 				&mut bindings, fields, Attributes::Some(&[])
 			);
-			if let Some(fields) = fields { n_fields[2] = fields.len() }
-		}
-	} }
-	
-	if let Some(strukt) = structs.first() {
-		let syn::Fields::Named(fields) = &strukt.fields else { panic!() };
-		if n_fields[0] == fields.named.len() {
-			structs.swap_remove(0);
-			if errable {
-				objects.extend(syn::Error::new(
-					Span::call_site(), "these arguments would declare a \
-					struct that does not reference any item in the view"
-				).into_compile_error())
+			if let Some(fields) = fields { n_fields[1] = fields.len() }
+			
+			let inferred = item::take_inferred();
+			if let Some(strukt) = strukt.as_mut().or(structs.first_mut()) {
+				strukt.generics.params.extend(inferred.into_iter().map(|param| syn::GenericParam::Type(param.into())))
 			}
 		}
-	}
+		Root::Error(error) => objects.extend(error.into_compile_error())
+	} }
 	
 	check_struct! { }
-	for constr in constrs.into_iter().rev() { constr.extend_into(&mut objects) }
-	objects.extend(settings); (objects, bindings)
+	crate::content::check_parents(0, &mut objects, &constrs);
+	crate::Construction::extend_all(constrs, &mut objects);
+	objects.extend(settings);
+	crate::content::finish(&mut objects);
+	(objects, bindings)
 }
 
-pub enum Visitor {
+pub enum Visitor<'a> {
 	Error(syn::Error), Ok {
-		structs: Vec<syn::ItemStruct>,
-		errable: bool,
-		  deque: std::collections::VecDeque<(Range, TokenStream, Bindings)>
+		    structs: Vec<syn::ItemStruct>,
+		  component: bool,
+		      deque: std::collections::VecDeque<(Range, TokenStream, Bindings)>,
+		      views: usize,
+		  inferable: bool,
+		placeholder: &'a str,
 	}
 }
 
 macro_rules! item {
-	($visit:ident, $item:ident) => {
+	($visit:ident, $item:ident) => { item!($visit, $item, syn::$item::Verbatim(TokenStream::new())); };
+	($visit:ident, $item:ident, $empty:expr) => {
 		fn $visit(&mut self, node: &mut syn::$item) {
-			let Self::Ok { structs, errable, deque } = self else { return };
+			let Self::Ok { structs, deque, views, inferable, .. } = self else { return };
 			
 			if let syn::$item::Macro(mac) = node {
 				if mac.mac.path.is_ident("view") {
@@ -178,10 +373,10 @@ macro_rules! item {
 					if mac.mac.tokens.is_empty() {
 						return *self = Self::Error(range.error("this view has no content"))
 					}
-					return match mac.mac.parse_body().map(|root| expand(structs, root, *errable)) {
+					return match mac.mac.parse_body().map(|root| expand(structs, root, *inferable)) {
 						Ok((stream, bindings)) => {
-							deque.push_back((range, stream, bindings));
-							*node = syn::$item::Verbatim(TokenStream::new())
+							deque.push_back((range, stream, bindings)); *views += 1;
+							*node = $empty
 						}
 						Err(error) => *self = Self::Error(error)
 					}
@@ -192,54 +387,280 @@ macro_rules! item {
 	}
 }
 
-impl VisitMut for Visitor {
+/// Whether the `impl` is of `declarative::component::Component` (or of any trait named `Component`
+/// with the `component` key, e.g. if imported) without a `fn view`, so that it is generated from its `view!`.
+fn component(node: &syn::ItemImpl, opted: bool) -> bool {
+	let Some((_, path, _)) = &node.trait_ else { return false };
+	let (string, krate) = (quote![#path].to_string(), crate::krate());
+	let component = quote![#krate::component::Component].to_string();
+	
+	(opted && path.segments.last().is_some_and(|segment| segment.ident == "Component")
+		|| string.trim_start_matches(":: ") == component.trim_start_matches(":: "))
+		&& !node.items.iter().any(|item| matches!(item, syn::ImplItem::Fn(fun) if fun.sig.ident == "view"))
+}
+
+/// Whether an item of the view is named `root` (items are the `let` statements of the expansion).
+fn has_root(stream: &TokenStream) -> bool {
+	let Ok(stmts) = syn::parse::Parser::parse2(syn::Block::parse_within, stream.clone()) else { return false };
+	
+	stmts.iter().any(|stmt| match stmt {
+		syn::Stmt::Local(syn::Local { pat: syn::Pat::Ident(pat), .. }) => pat.ident == "root",
+		syn::Stmt::Local(syn::Local { pat: syn::Pat::Type(syn::PatType { pat, .. }), .. }) =>
+			matches!(&**pat, syn::Pat::Ident(pat) if pat.ident == "root"),
+		_ => false
+	})
+}
+
+impl VisitMut for Visitor<'_> {
 	item!(visit_foreign_item_mut, ForeignItem);
 	item!(visit_impl_item_mut, ImplItem);
 	item!(visit_item_mut, Item);
+	item!(visit_stmt_mut, Stmt, syn::Stmt::Item(syn::Item::Verbatim(TokenStream::new())));
 	item!(visit_trait_item_mut, TraitItem);
 	
 	fn visit_item_impl_mut(&mut self, node: &mut syn::ItemImpl) {
-		if let Self::Ok { structs, .. } = self {
-			if let syn::Type::Path(path) = node.self_ty.as_ref() {
-				path.path.get_ident().map(|ident| structs.first_mut().map(|strukt| {
-					strukt.attrs = node.attrs.clone();
-					if strukt.generics.lt_token.is_none() {
-						strukt.generics = node.generics.clone()
+		let mut inferable = None;
+		
+		if let Self::Ok { structs, inferable: outer, .. } = self {
+			if let (Some(strukt), syn::Type::Path(syn::TypePath { qself: None, path })) = (structs.first_mut(), node.self_ty.as_ref()) {
+				inferable = Some(std::mem::replace(outer, false));
+				strukt.attrs = node.attrs.clone();
+				
+				if strukt.ident == "_" {
+					if path.leading_colon.is_some() || path.segments.iter().rev().skip(1).any(|segment| segment.ident != "self") {
+						return *self = Self::Error(syn::Error::new_spanned(path, "the struct of this view would be declared \
+							next to this `impl`, not in the module of this type, so the `impl` must be written in that module \
+							(or the struct must be named in the attribute, e.g. `#[view(struct = Name)]`)"))
+					}
+					strukt.ident = path.segments.last().unwrap().ident.clone()
+				}
+				if strukt.generics.lt_token.is_none() {
+					let syn::Fields::Named(fields) = &strukt.fields else { panic!() };
+					if let Err(error) = inherit(&mut strukt.generics, &fields.named, &node.generics, path) {
+						return *self = Self::Error(error)
 					}
-					if strukt.ident == "_" { strukt.ident = ident.clone() }
-				}));
+				}
+			} else if let Some(strukt) = structs.first_mut().filter(|strukt| strukt.ident == "_") {
+				strukt.ident.set_span(syn::spanned::Spanned::span(&node.self_ty))
+			}
+		}
+		let (views, len) = if let Self::Ok { views, deque, .. } = self { (*views, deque.len()) } else { (0, 0) };
+		syn::visit_mut::visit_item_impl_mut(self, node);
+		if let (Self::Ok { inferable: inner, .. }, Some(outer)) = (&mut *self, inferable) { *inner = outer }
+		
+		let Self::Ok { deque, views: after, component: opted, placeholder, .. } = self else { return };
+		if *after == views || !component(node, *opted) { return }
+		
+		let span = syn::spanned::Spanned::span(&node.trait_.as_ref().unwrap().1);
+		
+		let Some((range, stream, bindings)) = deque.back().filter(|_| deque.len() > len) else {
+			return *self = Self::Error(syn::Error::new(span, "`fn view` can only be generated from a `view!` \
+				written as an item of this `impl` (not as a statement of a function)"))
+		};
+		if !has_root(stream) {
+			return *self = Self::Error(Range(range.0, range.1).error("the view of a component must have an item named `root`, \
+				which is given as the root of the `View` (e.g. `gtk::Box root { }`)"))
+		}
+		
+		// with a `view!` and no `fn view`, `impl Component` gets one whose bindings refresh the view
+		let placeholder = syn::Ident::new(placeholder, Span::call_site());
+		let refresh = if bindings.spans.is_empty() { quote![|_: &Self| {}] } else { quote![move |state: &Self| bindings!()] };
+		let krate = crate::krate();
+		
+		node.items.push(syn::parse_quote_spanned! { span =>
+			fn view(&self, sender: &#krate::component::Sender<Self::Msg>)
+			-> #krate::component::View<Self> {
+				#[allow(unused_variables)]
+				let (state, sender) = (self, ::core::clone::Clone::clone(sender));
+				#placeholder! { }
+				#krate::component::View {
+					root: ::core::clone::Clone::clone(&root),
+					refresh: ::std::boxed::Box::new(#refresh),
+				}
+			}
+		})
+	}
+	
+	fn visit_block_mut(&mut self, node: &mut syn::Block) {
+		let mut views = vec![];
+		
+		for (index, stmt) in node.stmts.iter_mut().enumerate() {
+			let view = matches!(stmt, syn::Stmt::Macro(mac) if mac.mac.path.is_ident("view"));
+			self.visit_stmt_mut(stmt);
+			
+			let Self::Ok { deque, .. } = self else { return };
+			if view { views.push((index, deque.pop_back().unwrap())) }
+		}
+		
+		let Self::Ok { placeholder, .. } = self else { return };
+		
+		for (index, (range, stream, bindings)) in views {
+			let (before, after) = node.stmts.split_at_mut(index + 1);
+			let mut errors = TokenStream::new();
+			
+			parse(&mut |visitor| for stmt in &mut *after {
+				visitor.visit_stmt_mut(stmt)
+			}, &mut errors, range, stream, bindings, placeholder);
+			
+			before[index] = syn::Stmt::Item(syn::Item::Verbatim(errors))
+		}
+	}
+}
+
+fn inherit(
+	generics: &mut syn::Generics, fields: &Punctuated<syn::Field, syn::Token![,]>, from: &syn::Generics, path: &syn::Path
+) -> syn::Result<()> {
+	let mut kept = Mentions::new(&from.params);
+	syn::visit::Visit::visit_path(&mut kept, path);
+	
+	for field in fields { // those of `fields(...)`
+		let mut mentions = Mentions::new(&from.params);
+		syn::visit::Visit::visit_type(&mut mentions, &field.ty);
+		
+		let Some((param, _)) = from.params.iter().zip(mentions.found.iter().zip(&kept.found))
+			.find(|(_, (found, kept))| **found && !**kept) else { continue };
+		
+		let param = match param {
+			syn::GenericParam::Lifetime(param) => param.lifetime.to_string(),
+			syn::GenericParam::Type(param) => param.ident.to_string(),
+			syn::GenericParam::Const(param) => param.ident.to_string(),
+		};
+		Err(syn::Error::new_spanned(&field.ty, format!("`{param}` is not a parameter of the struct \
+			because the type of the `impl` does not refer to it")))?
+	}
+	
+	let dropped = |visit: &dyn Fn(&mut Mentions)| {
+		let mut mentions = Mentions::new(&from.params); visit(&mut mentions);
+		mentions.found.iter().zip(&kept.found).any(|(found, kept)| *found && !kept)
+	};
+	
+	let params = from.params.iter().zip(&kept.found).filter(|(_, kept)| **kept).map(|(param, _)| {
+		let mut param = param.clone();
+		match &mut param { // bounds on dropped parameters are dropped too
+			syn::GenericParam::Lifetime(param) => param.bounds = std::mem::take(&mut param.bounds)
+				.into_iter().filter(|bound| !dropped(&|mentions| syn::visit::Visit::visit_lifetime(mentions, bound))).collect(),
+			syn::GenericParam::Type(param) => {
+				param.bounds = std::mem::take(&mut param.bounds).into_iter()
+					.filter(|bound| !dropped(&|mentions| syn::visit::Visit::visit_type_param_bound(mentions, bound))).collect();
+				
+				if param.default.as_ref().is_some_and(|ty| dropped(&|mentions| syn::visit::Visit::visit_type(mentions, ty))) {
+					(param.eq_token, param.default) = (None, None)
+				}
 			}
+			syn::GenericParam::Const(_) => (),
 		}
-		syn::visit_mut::visit_item_impl_mut(self, node)
+		param
+	}).collect::<Vec<_>>();
+	
+	if !params.is_empty() { (generics.lt_token, generics.gt_token) = (from.lt_token, from.gt_token) }
+	
+	let inferred = std::mem::take(&mut generics.params);
+	generics.params = params.into_iter().chain(inferred).collect();
+	
+	let Some(clause) = &from.where_clause else { return Ok(()) };
+	let predicates = clause.predicates.iter()
+		.filter(|predicate| !dropped(&|mentions| syn::visit::Visit::visit_where_predicate(mentions, predicate)));
+	let predicates: Punctuated<_, _> = predicates.cloned().collect();
+	
+	if !predicates.is_empty() {
+		generics.where_clause = Some(syn::WhereClause { where_token: clause.where_token, predicates })
+	}
+	Ok(())
+}
+
+/// Finds the generic parameters of an `impl` mentioned by a type or bound: lifetimes by name
+/// unless bound by a `for<...>`, and types or constants by the first segment of a relative path
+/// (so the `T` of `Trait::T`, `<X>::T` or `::T` is not the parameter).
+struct Mentions<'a> { params: &'a Punctuated<syn::GenericParam, syn::Token![,]>, found: Vec<bool>, shadowed: Vec<syn::Ident> }
+
+impl<'a> Mentions<'a> {
+	fn new(params: &'a Punctuated<syn::GenericParam, syn::Token![,]>) -> Self {
+		Self { params, found: vec![false; params.len()], shadowed: vec![] }
+	}
+	
+	fn shadow(&mut self, lifetimes: Option<&syn::BoundLifetimes>, visit: impl FnOnce(&mut Self)) {
+		let len = self.shadowed.len();
+		
+		self.shadowed.extend(lifetimes.into_iter().flat_map(|lifetimes| &lifetimes.lifetimes).filter_map(|param| match param {
+			syn::GenericParam::Lifetime(param) => Some(param.lifetime.ident.clone()), _ => None
+		}));
+		visit(self);
+		self.shadowed.truncate(len)
+	}
+}
+
+impl<'ast> syn::visit::Visit<'ast> for Mentions<'_> {
+	fn visit_lifetime(&mut self, node: &'ast syn::Lifetime) {
+		if self.shadowed.contains(&node.ident) { return }
+		
+		for (param, found) in self.params.iter().zip(&mut self.found) {
+			if matches!(param, syn::GenericParam::Lifetime(param) if param.lifetime.ident == node.ident) { *found = true }
+		}
+	}
+	
+	fn visit_path(&mut self, node: &'ast syn::Path) {
+		if let (None, Some(first)) = (node.leading_colon, node.segments.first()) {
+			for (param, found) in self.params.iter().zip(&mut self.found) {
+				if match param {
+					syn::GenericParam::Type(param) => param.ident == first.ident,
+					syn::GenericParam::Const(param) => param.ident == first.ident,
+					syn::GenericParam::Lifetime(_) => false,
+				} { *found = true }
+			}
+		}
+		syn::visit::visit_path(self, node)
+	}
+	
+	fn visit_type_path(&mut self, node: &'ast syn::TypePath) {
+		let Some(qself) = &node.qself else { return syn::visit::visit_type_path(self, node) };
+		self.visit_type(&qself.ty);
+		
+		for segment in node.path.segments.iter().take(qself.position) { self.visit_path_segment(segment) }
+		for segment in node.path.segments.iter().skip(qself.position) { self.visit_path_arguments(&segment.arguments) }
+	}
+	
+	fn visit_trait_bound(&mut self, node: &'ast syn::TraitBound) {
+		self.shadow(node.lifetimes.as_ref(), |this| syn::visit::visit_trait_bound(this, node))
+	}
+	
+	fn visit_type_bare_fn(&mut self, node: &'ast syn::TypeBareFn) {
+		self.shadow(node.lifetimes.as_ref(), |this| syn::visit::visit_type_bare_fn(this, node))
+	}
+	
+	fn visit_predicate_type(&mut self, node: &'ast syn::PredicateType) {
+		self.shadow(node.lifetimes.as_ref(), |this| syn::visit::visit_predicate_type(this, node))
 	}
 }
 
-pub fn parse(item: &mut syn::Item,
+pub fn parse(visit: &mut dyn FnMut(&mut crate::Visitor),
            output: &mut TokenStream,
             range: Range,
      mut   stream: TokenStream,
      mut bindings: Bindings,
+      placeholder: &str,
 ) {
 	let mut visitor = crate::Visitor::Ok {
-		items: None, assignee: &mut None, placeholder: "expand_view_here", stream: &mut stream
+		items: None, assignee: &mut None, placeholder, stream: &mut stream
 	};
-	visitor.visit_item_mut(item);
+	visit(&mut visitor);
 	
-	const ERROR: &str = "views must be consumed with the `expand_view_here!` placeholder macro";
 	match visitor.stream_is_empty() {
-		Ok(empty) => if !empty { stream.extend(range.error(ERROR).into_compile_error()) },
-		Err(error) => stream.extend(error.into_compile_error()),
+		Ok(empty) => if !empty {
+			let error = format!("views must be consumed with the `{placeholder}!` placeholder macro");
+			output.extend(range.error(&error).into_compile_error())
+		},
+		Err(error) => output.extend(error.into_compile_error()),
 	}
 	
 	if !bindings.spans.is_empty() {
 		let mut visitor = crate::Visitor::Ok {
 			items: None, assignee: &mut None, placeholder: "bindings", stream: &mut bindings.stream
 		};
-		visitor.visit_item_mut(item);
+		visit(&mut visitor);
 		
 		match visitor.stream_is_empty() {
 			Ok(empty) => if !empty { crate::bindings_error(output, bindings.spans) },
-			Err(error) => stream.extend(error.into_compile_error()),
+			Err(error) => output.extend(error.into_compile_error()),
 		}
 	}
 }
@@ -256,7 +677,7 @@ pub fn display_ty(ty: &syn::TypePath, f: &mut std::fmt::Formatter<'_>) -> std::f
 
 impl<'a> crate::Assignee<'a> {
 	pub fn spanned_to(&'a self, span: Span) -> impl Iterator<Item = syn::Ident> + 'a {
-		self.iter().cloned().map(move |mut ident| { ident.set_span(span); ident })
+		self.iter().cloned().map(move |mut ident| { ident.set_span(ident.span().located_at(span)); ident })
 	}
 	
 	fn iter(&'a self) -> Box<dyn Iterator<Item = &'a syn::Ident> + 'a> {
@@ -299,17 +720,140 @@ impl<T: AsRef<[syn::Attribute]>> Attributes<T> {
 	}
 }
 
+/// In document order, constructions are not sorted by their references, so those to an item
+/// constructed later (by a previous construction) are reported. Other items (e.g. function calls)
+/// may refer to a variable outside the view with the same name, as they always could.
+fn forward_references(objects: &mut TokenStream, errors: &mut TokenStream) {
+	let Ok(mut stmts) = syn::parse::Parser::parse2(syn::Block::parse_within, objects.clone()) else { return };
+	
+	let names: Vec<_> = stmts.iter().map(|stmt| {
+		let syn::Stmt::Local(syn::Local { pat, init: Some(init), .. }) = stmt else { return None };
+		if !matches!(&*init.expr, syn::Expr::Macro(mac) if mac.mac.path.is_ident("construct")) { return None }
+		
+		let syn::Pat::Ident(syn::PatIdent { ident, .. }) = (match pat {
+			syn::Pat::Type(pat) => &*pat.pat, pat => pat
+		}) else { return None };
+		Some(ident.clone())
+	}).collect();
+	
+	for (index, stmt) in stmts.iter_mut().enumerate() {
+		let (syn::Stmt::Local(stmt), Some(_)) = (stmt, &names[index]) else { continue };
+		let Some(init) = &stmt.init else { continue };
+		let expr = &init.expr;
+		
+		let later: Vec<_> = names[index + 1..].iter().flatten().filter(|name| {
+			let spans = crate::references(&quote![#expr], name);
+			
+			for &span in &spans {
+				errors.extend(syn::Error::new(span, format!("`{name}` is constructed later in document order, \
+					so it cannot be referenced here (write `{name}` before, or `~<` in the content of this \
+					item so that it is constructed after the items in document order)")).into_compile_error())
+			}
+			!spans.is_empty()
+		}).collect();
+		
+		if !later.is_empty() { stub(stmt, &later) }
+	}
+	*objects = quote![#(#stmts)*]
+}
+
+/// Declares unreachable variables in the initializer of a construction for the
+/// items it references that are constructed later, so that they are not reported
+/// again as missing values (their references are already reported).
+fn stub(stmt: &mut syn::Local, names: &[&syn::Ident]) {
+	let Some(init) = &mut stmt.init else { return };
+	let expr = &init.expr;
+	*init.expr = syn::parse_quote![{ #(let #names = ::core::unreachable!();)* #expr }];
+	stmt.attrs.push(syn::parse_quote![#[allow(unreachable_code)]])
+}
+
 impl crate::Construction {
+	pub fn extend_all(constrs: Vec<Self>, objects: &mut TokenStream) {
+		let names: Vec<_> = constrs.iter().map(Self::name).collect();
+		let mut errors = TokenStream::new();
+		
+		if crate::document() { forward_references(objects, &mut errors) }
+		
+		let dependencies: Vec<Vec<usize>> = constrs.iter().enumerate().map(|(this, constr)| {
+			names.iter().enumerate().filter(|&(index, name)| index != this && name.as_ref()
+				.is_some_and(|name| !crate::references(constr.right(), name).is_empty()))
+				.map(|(index, _)| index).collect()
+		}).collect();
+		
+		let (mut order, mut done) = (Vec::with_capacity(constrs.len()), vec![false; constrs.len()]);
+		
+		while order.len() < constrs.len() {
+			let ready = (0..constrs.len()).rev().find(|&index| !done[index]
+				&& dependencies[index].iter().all(|&dependency| done[dependency]));
+			
+			if let Some(index) = ready { order.push(index); done[index] = true; continue }
+			
+			let reaches = |from: usize, to: usize| {
+				let (mut stack, mut seen) = (vec![from], vec![false; done.len()]);
+				
+				while let Some(index) = stack.pop() {
+					if index == to { return true }
+					if std::mem::replace(&mut seen[index], true) { continue }
+					stack.extend(dependencies[index].iter().filter(|&&dependency| !done[dependency]))
+				}
+				false
+			};
+			
+			for index in (0..constrs.len()).rev().filter(|&index| !done[index]) {
+				let others: Vec<_> = dependencies[index].iter()
+					.filter(|&&dependency| !done[dependency] && reaches(dependency, index))
+					.map(|&dependency| format!("`{}`", names[dependency].as_ref().unwrap())).collect();
+				
+				let name = names[index].as_ref().unwrap();
+				
+				if !others.is_empty() { errors.extend(syn::Error::new(name.span(), format!(
+					"`{name}` cannot be constructed because it references {} which in turn \
+					depends on it (circular dependency)", others.join(", ")
+				)).into_compile_error()) }
+			}
+			order.extend((0..constrs.len()).rev().filter(|&index| !done[index]))
+		}
+		
+		objects.extend(errors);
+		let mut constrs: Vec<_> = constrs.into_iter().map(Some).collect();
+		
+		for index in order {
+			let later: Vec<_> = dependencies[index].iter().filter(|&&dependency| constrs[dependency].is_some())
+				.map(|&dependency| names[dependency].as_ref().unwrap()).collect(); // circular dependencies
+			
+			let mut stream = TokenStream::new();
+			constrs[index].take().unwrap().extend_into(&mut stream);
+			
+			match syn::parse2(stream.clone()) {
+				Ok(syn::Stmt::Local(mut local)) if !later.is_empty() => { stub(&mut local, &later); objects.extend(quote![#local]) }
+				_ => objects.extend(stream)
+			}
+		}
+	}
+	
+	fn name(&self) -> Option<syn::Ident> {
+		let (Self::BuilderPattern { left, .. } | Self::StructLiteral { left, .. }) = self;
+		let mut trees = left.clone().into_iter().skip_while(|tree| !matches!(tree, TokenTree::Ident(let_) if let_ == "let"));
+		
+		trees.next()?;
+		trees.find_map(|tree| match tree { TokenTree::Ident(ident) if ident != "mut" => Some(ident), _ => None })
+	}
+	
+	pub fn right(&self) -> &TokenStream {
+		match self { Self::BuilderPattern { right, .. } => right, Self::StructLiteral { fields, .. } => fields }
+	}
+	
 	pub fn extend_into(self, objects: &mut TokenStream) {
 		match self {
-			Self::BuilderPattern { left, right, span, tilde } => {
+			Self::BuilderPattern { left, right, span, tilde, .. } => {
 				objects.extend(left);
 				objects.extend(quote::quote_spanned! {
 					span => construct!(#tilde #right)
 				})
 			}
-			Self::StructLiteral { left, ty, fields, span, tilde } => {
+			Self::StructLiteral { left, ty, mut fields, rest, span, tilde } => {
 				objects.extend(left);
+				fields.extend(rest.map(|rest| quote::quote_spanned![rest.spans[0] => ..]));
 				
 				let mut fields = Group::new(Delimiter::Brace, fields);
 				fields.set_span(span);
@@ -321,6 +865,28 @@ impl crate::Construction {
 		}
 		objects.append(Punct::new(';', Spacing::Alone))
 	}
+	
+	/// Explains how the item is expanded and how to write the property otherwise.
+	pub fn within(&self) -> &'static str {
+		match self {
+			Self::BuilderPattern { origin: crate::Origin::Braces, .. } =>
+				"this item, which is expanded in a builder pattern because `}` is not followed by `!` \
+				 (add `!` after `}` to construct it with `construct!(? Type)` and then call its methods, \
+				 or write `~` before the property so that it is set after building)",
+			Self::BuilderPattern { origin: crate::Origin::Call, .. } =>
+				"this item, which is expanded in a builder pattern because `}` follows a function call \
+				 and is followed by `!` (remove `!` to call the methods of the returned value, \
+				 or write `~` before the property so that it is set after building)",
+			Self::BuilderPattern { origin: crate::Origin::Back, .. } =>
+				"this 'back, whose methods are chained because `}` is not followed by `!` \
+				 (add `!` after `}` to call the methods of the returned value, \
+				 or write `~` before the property so that it is set after building)",
+			Self::StructLiteral { .. } =>
+				"this item, which is expanded as a struct literal because `}` is followed by `?` \
+				 (remove `?` to expand it in a builder pattern, \
+				 or write `~` before the property so that it is set after the struct literal)",
+		}
+	}
 }
 
 impl crate::Path {
@@ -0,0 +1,25 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[deny(dead_code, unused_mut)] // the attributes below would not compile if applied to the wrong target
+mod parts {
+	#[declarative_macros::view(struct = pub Parts, derive(Debug))]
+	impl Parts {
+		view! {
+			#[stmt(allow(unused_mut))] #[field(allow(dead_code))]
+			String::from("text") pub mut text as String { }
+		}
+		pub fn new() -> Self {
+			expand_view_here! { }
+			Self { text }
+		}
+	}
+}
+
+#[test]
+fn attributes() {
+	assert_eq!(format!("{:?}", parts::Parts::new()), "Parts { text: \"text\" }");
+}
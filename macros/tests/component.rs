@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative::{component::{self, Blocking}, html::{self, construct}};
+use declarative_macros::view;
+
+enum Msg { Increase }
+
+struct Counter { count: u32 }
+
+#[view] // the path of the trait is enough
+impl declarative::component::Component for Counter {
+	type Msg = Msg;
+	type Init = u32;
+	type Root = html::Element;
+	
+	fn init(count: u32) -> Self { Self { count } }
+	
+	fn update(&mut self, Msg::Increase: Msg) { self.count += 1 }
+	
+	view! {
+		html::P root {
+			'bind #set_text: &format!("{}", state.count)
+		}
+	}
+}
+
+struct Static;
+
+#[view(component)] // needed for an imported trait
+impl component::Component for Static {
+	type Msg = ();
+	type Init = ();
+	type Root = html::Element;
+	
+	fn init((): ()) -> Self { Self }
+	
+	fn update(&mut self, (): ()) { }
+	
+	view!(html::P root { text: "static" }); // without bindings
+}
+
+trait Component { fn text() -> String; } // not a component
+
+trait Describe { fn describe() -> String; }
+
+#[view(component)] // not a trait named `Component`, so `fn view` is not generated
+impl Describe for Static {
+	view!(String::from("description") description { });
+	
+	fn describe() -> String {
+		expand_view_here! { }
+		description
+	}
+}
+
+#[view]
+impl Component for Static {
+	view!(String::from("text") text { });
+	
+	fn text() -> String {
+		expand_view_here! { }
+		text
+	}
+}
+
+#[test]
+fn component() {
+	let runtime = Blocking::default();
+	let counter = component::run::<Counter>(&runtime, 1);
+	assert_eq!(counter.root.to_string(), "<p>1</p>");
+	
+	counter.sender.send(Msg::Increase);
+	runtime.run_pending();
+	assert_eq!(counter.root.to_string(), "<p>2</p>");
+	
+	let fixed = component::run::<Static>(&runtime, ());
+	fixed.sender.send(());
+	runtime.run_pending();
+	assert_eq!(fixed.root.to_string(), "<p>static</p>");
+	
+	assert_eq!(<Static as Component>::text(), "text");
+	assert_eq!(Static::describe(), "description");
+}
+
+struct Worker { done: Vec<u32> }
+
+#[view(component)]
+impl component::Component for Worker {
+	type Msg = u32;
+	type Init = ();
+	type Root = html::Element;
+	
+	fn init((): ()) -> Self { Self { done: vec![] } }
+	
+	fn update(&mut self, msg: u32) { self.done.push(msg) }
+	
+	view! {
+		html::P root {
+			'bind #set_text: &format!("{:?}", state.done)
+		}
+		std::thread::spawn({ // `sender` is the one of the view
+			let sync = sender.sync().unwrap();
+			move || sync.send(1)
+		}) thread { #[allow(unused_must_use)] join; } // before the view is returned
+	}
+}
+
+#[test]
+fn threads() {
+	let runtime = Blocking::default();
+	let worker = component::run_sync::<Worker>(&runtime, ());
+	
+	let sync = worker.sender.sync().unwrap();
+	std::thread::spawn(move || sync.send(2)).join().unwrap();
+	runtime.run_pending();
+	assert_eq!(worker.root.to_string(), "<p>[1, 2]</p>");
+	
+	let local = component::run::<Counter>(&runtime, 0);
+	assert!(local.sender.sync().is_none()); // `Msg` is not required to be `Send`
+}
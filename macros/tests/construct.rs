@@ -6,12 +6,13 @@
 
 #![allow(unused_variables, dead_code)]
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Test { field: Option<Box<Test>> }
 
 impl Test {
 	fn builder() -> Builder { Builder }
 	fn method(&self) { }
+	fn scope(&self, scope: impl FnOnce(&Test)) { scope(self) }
 	fn start(self) -> Self { self }
 }
 
@@ -19,9 +20,16 @@ struct Builder;
 
 impl Builder {
 	fn building(self) -> Self { self }
+	fn scoped(self, scope: impl FnOnce(&Test)) -> Self { scope(&Test::default()); self }
 	fn build(self) -> Test { Test { field: None } }
 }
 
+impl declarative::Downgrade for Test { // for 'state
+	type Weak = Test;
+	fn downgrade(&self) -> Test { self.clone() }
+	fn upgrade_from(weak: &Test) -> Option<Test> { Some(weak.clone()) }
+}
+
 #[test]
 fn construct() {
 	macro_rules! construct {
@@ -37,6 +45,10 @@ fn construct() {
 		( $type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)*.build() };
 	}
 	
+	macro_rules! twice { ($builder:expr) => { $builder.building().building() } }
+	macro_rules! call { ($test:expr, $times:expr) => { for _ in 0..$times { $test.method() } } }
+	macro_rules! repeat { ($test:expr, $times:literal $body:block) => { for _ in 0..$times $body } }
+	
 	declarative_macros::block! {
 		Test default_1 { method; method; }!
 		Test::default() default_2 { method; method; }
@@ -60,5 +72,31 @@ fn construct() {
 		Test inter_3 { field: Some(_.into()) @ Test { } ~ method; }? // start
 		Test inter_4 { field: Some(_.into()) @ Test { } ~~ }? // does not start
 		Test inter_5 { field: Some(_.into()) @ Test { } ~~ method; }? // does not start
+		
+		Test with_1 { scoped: 'with test { method; } }
+		Test with_2 { scope: 'with test { scope: 'with inner { method; } } }!
+		Test::default() with_3 { scope: 'with move test { method; } }
+		
+		Test parent_1 { field: Some(_.into()) @ Test { 'parent parent } }? // reference
+		Test parent_2 { field: Some(_.into()) @ Test { 'parent { method; } } }? // edit
+		
+		Test fallback_1 { building; #[allow(unused)] method; } // called after building
+		Test fallback_2 { building; Test::method &; } // the same
+		
+		Test::builder() macro_1 { twice!; build; ~~ call!: 2 }!
+		Test macro_2 { building; twice!; ~ call!: 2; }
+		
+		Test directive_1 { 'repeat 2 { method; } }!
+		Test directive_2 { building; ~ 'repeat 2 { method; } }
+		
+		Test state_1 { 'state count: u8 = 0; 'bind #if *count > 0 { method; } }!
+		Test consume_1 { 'bind method; 'consume refresh => std::convert::identity }!
+	}
+	
+	declarative_macros::block! {
+		order = document;
+		Test document_1 { field: Some(_.into()) @ Test { } }? // child first
+		Test document_2 { building; ~ method; } // at `~`
+		Test document_3 { building; ~< method; } // in reverse
 	}
 }
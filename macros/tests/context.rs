@@ -0,0 +1,65 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::block;
+
+#[derive(Clone)]
+struct Theme(&'static str);
+
+mod runtime { pub use declarative::context; } // e.g. a re-export
+
+fn child() -> String {
+	block! { String::new() mut root { 'inject theme: Theme = Theme("default") push_str: theme.0 } }
+	root
+}
+
+#[test]
+fn context() {
+	let condition = true;
+	
+	block! {
+		Vec::new() mut children {
+			if condition { // provided until the end of the condition
+				'provide theme = Theme("nested")
+				push: child()
+			}
+			push: child()
+		}
+	}
+	assert_eq!(children, ["nested", "default"]);
+	
+	block! {
+		Vec::new() mut children {
+			'provide theme = Theme("dark") // before the rest of the content
+			push: child()
+			push: _ @ child() item { }
+		}
+	}
+	assert_eq!(children, ["dark", "dark"]);
+	assert_eq!(child(), "default"); // provided until the end of the view
+	
+	block! {
+		crate = self::runtime;
+		Vec::new() mut children {
+			'provide theme = Theme("renamed")
+			push: child()
+		}
+	}
+	assert_eq!(children, ["renamed"]);
+}
+
+#[derive(Debug, PartialEq)]
+struct Injecting(u8);
+
+impl Clone for Injecting {
+	fn clone(&self) -> Self { Injecting(self.0 + declarative::context::try_inject::<u8>().unwrap_or(0)) }
+}
+
+#[test]
+fn reentrant() {
+	let (_first, _second) = (declarative::context::provide(1_u8), declarative::context::provide(Injecting(1)));
+	assert_eq!(declarative::context::inject::<Injecting>(), Injecting(2));
+}
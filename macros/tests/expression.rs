@@ -0,0 +1,30 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::block;
+
+fn len(text: String) -> usize { text.len() }
+
+#[test]
+fn expression() {
+	let root = block!(=> String::from("root") mut text { push: '!' });
+	assert_eq!(root, "root!");
+	
+	assert_eq!(len(block!(=> String::new() mut text { push_str: "argument" })), 8);
+	
+	let (first, second) = block! {
+		=> (first, second);
+		String::from("first") first { }
+		String::from("second") mut second { push: '!' }
+	};
+	assert_eq!((first.as_str(), second.as_str()), ("first", "second!"));
+	
+	let mut update = block! {
+		=> move |more: &str| { bindings!(); text.clone() };
+		String::new() mut text { 'bind push_str: more }
+	};
+	assert_eq!((update("a"), update("b")), ("a".into(), "ab".into()));
+}
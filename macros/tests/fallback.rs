@@ -0,0 +1,45 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::block;
+use std::cell::RefCell;
+
+struct Node(RefCell<Vec<&'static str>>);
+
+struct Builder(Vec<&'static str>);
+
+trait Extra { fn extra(&self, text: &'static str); }
+
+impl Node {
+	fn builder() -> Builder { Builder(vec![]) }
+	fn method(&self, text: &'static str) { self.0.borrow_mut().push(text) }
+}
+
+impl Extra for Node {
+	fn extra(&self, text: &'static str) { self.0.borrow_mut().push(text) }
+}
+
+impl Builder {
+	fn chained(mut self, text: &'static str) -> Self { self.0.push(text); self }
+	fn build(self) -> Node { Node(RefCell::new(self.0)) }
+}
+
+macro_rules! construct {
+	($type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* .build() }
+}
+
+#[test]
+fn fallback() {
+	block! { // what cannot be chained is called after building, in order
+		Node node {
+			chained: "first"
+			#[cfg(all())] method: "attribute"
+			Extra::extra &: "path"
+			chained: "second"
+		}
+	}
+	assert_eq!(node.0.take(), ["first", "second", "attribute", "path"]);
+}
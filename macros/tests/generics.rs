@@ -0,0 +1,29 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::view;
+
+trait Named { const NAME: &'static str; }
+
+impl Named for u8 { const NAME: &'static str = "u8"; }
+
+// `U` and `'b` are not in the type, so they and what mentions them are dropped;
+// (like the `From<U>` bound of `T`) but the predicate with `for<'c>` is kept
+#[view(fields(name: &'a str, data: Vec<T>))]
+impl<'a, 'b: 'a, T: Named + Clone + From<U>, U> From<(&'a str, &'b U)> for self::Item<'a, T>
+where for<'c> fn(&'c T): Copy, U: Copy + Into<T> {
+	fn from((name, data): (&'a str, &'b U)) -> Self {
+		expand_view_here! { }
+		Self { name, data: vec![T::from(*data)], text }
+	}
+	view!(String::from(T::NAME) ref text { });
+}
+
+#[test]
+fn generics() {
+	let item = Item::<u8>::from(("item", &1u8));
+	assert_eq!((item.name, item.data, item.text.as_str()), ("item", vec![1], "u8"));
+}
@@ -0,0 +1,44 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![allow(non_snake_case)]
+
+use declarative_macros::view;
+
+fn make(text: &str) -> String { text.into() }
+
+#[derive(Default)]
+struct List(Vec<u8>);
+
+impl List {
+	fn push(&mut self, value: u8) -> usize { self.0.push(value); self.0.len() }
+}
+
+#[view(struct = pub Parts)]
+mod module {
+	use super::*;
+	
+	pub fn parts() -> Parts<String, String, usize> { // in the order of the items
+		expand_view_here! { }
+		Parts { a_b, aB, len }
+	}
+	
+	view! {
+		make("a") pub a_b { } // `a_b` and `aB` do not share a parameter
+		make("b") pub aB { }
+		
+		List::default() mut list {
+			push: 1
+			push: 2 'back pub len { }!
+		}
+	}
+}
+
+#[test]
+fn inference() {
+	let parts = module::parts();
+	assert_eq!((parts.a_b.as_str(), parts.aB.as_str(), parts.len), ("a", "b", 2));
+}
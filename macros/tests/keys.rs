@@ -0,0 +1,56 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![allow(dead_code)]
+
+use declarative_macros::view;
+
+#[view(struct = pub Parts<'a>, fields(name: &'a str), derive(Debug, Clone, PartialEq), placeholder = here)]
+impl<'a> Parts<'a> {
+	view!(String::from(name) ref mut text { push: '!' });
+	
+	fn new(name: &'a str) -> Self {
+		here! { }
+		Self { name, text }
+	}
+}
+
+#[view(pub Positional, name: &'static str)] // the old form
+impl Positional {
+	view!(String::from(name) ref text { });
+	
+	fn new(name: &'static str) -> Self {
+		expand_view_here! { }
+		Self { name, text }
+	}
+}
+
+#[view(derive(Debug), placeholder = there; struct Content { } String::new() ref mut text { push_str: "content" })]
+fn content() -> String {
+	there! { }
+	format!("{:?}", Content { text })
+}
+
+fn derive(text: &str) -> String { text.into() }
+
+#[view(derive("called") mut text { push: '!' })] // content that looks like a key
+fn called() -> String {
+	expand_view_here! { }
+	text
+}
+
+#[test]
+fn keys() {
+	let parts = Parts::new("parts");
+	assert_eq!(format!("{parts:?}"), "Parts { name: \"parts\", text: \"parts!\" }");
+	assert_eq!(parts.clone(), parts);
+	
+	let positional = Positional::new("positional");
+	assert_eq!((positional.name, positional.text.as_str()), ("positional", "positional"));
+	
+	assert_eq!(content(), "Content { text: \"content\" }");
+	assert_eq!(called(), "called!");
+}
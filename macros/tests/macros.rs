@@ -0,0 +1,73 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::block;
+use std::cell::RefCell;
+
+#[derive(Default)]
+struct Node(RefCell<Vec<String>>);
+
+struct Builder(Vec<String>);
+
+impl Node {
+	fn builder() -> Builder { Builder(vec![]) }
+	fn add(&self, text: &str) { self.0.borrow_mut().push(text.into()) }
+}
+
+impl Builder {
+	fn chained(mut self, text: &str) -> Self { self.0.push(text.into()); self }
+	fn build(self) -> Node { Node(RefCell::new(self.0)) }
+}
+
+macro_rules! construct {
+	(? $type:ty) => { <$type>::default() };
+	(~$type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* };
+	( $type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* .build() };
+	( $builder:expr) => { $builder.build() };
+}
+
+macro_rules! twice { // in a builder pattern
+	($builder:expr, $text:expr) => { $builder.chained($text).chained($text) }
+}
+
+macro_rules! width { // a custom directive close to 'with
+	($node:expr, $text:literal { $($body:tt)* }) => {{ $node.add($text); $($body)* }}
+}
+
+macro_rules! times { // receives the item
+	($node:expr, $text:expr, $times:expr) => { for _ in 0..$times { $node.add($text) } }
+}
+
+#[test]
+fn macros() {
+	block! {
+		Node typed { // the builder starts with the type
+			chained: "first"
+			twice!: "second"
+			~
+			times!: "third", 2
+		}
+		
+		Node::builder() called { // the builder is an expression
+			twice!: "first"
+			chained: "second"
+		}!
+		
+		Node::default() normal { times!: "first", 3 }
+	}
+	
+	assert_eq!(*typed.0.borrow(), ["first", "second", "second", "third", "third"]);
+	assert_eq!(*called.0.borrow(), ["first", "first", "second"]);
+	assert_eq!(*normal.0.borrow(), ["first", "first", "first"]);
+}
+
+#[test]
+fn directives() {
+	block! {
+		Node::default() node { 'width "first" { add: "second" } add: "third" }
+	}
+	assert_eq!(*node.0.borrow(), ["first", "second", "third"]);
+}
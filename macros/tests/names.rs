@@ -0,0 +1,28 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[test]
+fn names() {
+	// the same names that are generated for the unnamed items below, which cannot see them:
+	let (string_from_0, string_from_1) = ("c", "d");
+	
+	declarative_macros::block! {
+		String::new() mut first {
+			push_str: &_ @ String::from("a") { }
+			push_str: &_ @ String::from("b") { }
+			push_str: string_from_0
+		}
+	}
+	declarative_macros::block! { // numbered from zero again
+		String::new() mut second {
+			push_str: &_ @ String::from("e") { }
+			push_str: string_from_1
+		}
+	}
+	
+	assert_eq!(first, "abc");
+	assert_eq!(second, "ed");
+}
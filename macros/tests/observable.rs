@@ -0,0 +1,33 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![allow(unused_variables, dead_code)]
+
+use declarative_macros::Observable;
+
+#[derive(Default, Observable)]
+struct Test<T: Clone> {
+	count: u8,
+	items: Vec<T>,
+	#[observable(skip)] cache: String,
+	#[observable(changes)] changes: u64,
+}
+
+#[test]
+fn observable() {
+	let mut test = Test::<u8>::default();
+	assert!(!test.changed());
+	
+	test.set_count(1);
+	assert!(test.count_changed() && !test.items_changed());
+	
+	test.update_items(|items| items.push(2));
+	assert_eq!(test.take_changes(), Test::<u8>::COUNT_CHANGED | Test::<u8>::ITEMS_CHANGED);
+	assert!(!test.changed());
+	
+	test.change_all();
+	assert!(test.count_changed() && test.items_changed());
+}
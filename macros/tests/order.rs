@@ -0,0 +1,90 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![allow(unused_variables)]
+
+use declarative_macros::block;
+use std::cell::RefCell;
+
+thread_local!(static LOG: RefCell<Vec<String>> = const { RefCell::new(vec![]) });
+
+struct Node(String);
+
+struct Builder(String);
+
+impl Node {
+	fn builder() -> Builder { Builder(String::new()) }
+	fn copy(node: &Node) -> Self { Node(node.0.clone()) }
+}
+
+impl Builder {
+	fn name(mut self, name: &str) -> Self { self.0.push_str(name); self }
+	fn after(self, node: &Node) -> Self { assert!(!node.0.is_empty()); self }
+	fn each(self, each: impl Fn(&Node) -> usize) -> Self { each(&Node(String::new())); self }
+	fn build(self) -> Node { LOG.with_borrow_mut(|log| log.push(self.0.clone())); Node(self.0) }
+}
+
+macro_rules! construct {
+	($type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* .build() }
+}
+
+fn log() -> Vec<String> { LOG.take() }
+
+#[test]
+fn dependencies() {
+	block! { // constructed in reverse unless an item references another
+		Node first { name: "first" }
+		Node second { name: "second" after: &first }
+		Node third { name: "third" }
+	}
+	assert_eq!(log(), ["third", "first", "second"]);
+}
+
+#[test]
+fn shadowing() {
+	block! { // the parameter `first` does not refer to the item
+		Node first { name: "first" after: &second }
+		Node second { name: "second" each: |first| first.0.len() }
+	}
+	assert_eq!(log(), ["second", "first"]);
+	
+	block! { // nor the parameter of the setter of a 'state
+		Node value { name: "value" after: &other }
+		Node other { 'state count: u8 = 0 name: "other" }
+	}
+	assert_eq!(log(), ["other", "value"]);
+	
+	let later = Node(String::from("outer"));
+	
+	block! { // a function call refers to what is outside the view, even in document order
+		Node::copy(&later) normal { }
+		Node later { name: "later" }
+	}
+	assert_eq!((normal.0.as_str(), later.0.as_str()), ("outer", "later"));
+	
+	let later = Node(String::from("outer"));
+	
+	block! {
+		order = document;
+		Node::copy(&later) normal { }
+		Node later { name: "later" }
+	}
+	assert_eq!((normal.0.as_str(), later.0.as_str()), ("outer", "later"));
+	log();
+}
+
+#[test]
+fn document() {
+	block! { // children first, and those with `~<` in reverse at the end
+		order = document;
+		Node first { name: "first" }
+		Node second { name: "second" after: &first }
+		Node deferred { name: "deferred" ~< }
+		Node parent { name: "parent" after: &_ @ Node { name: "child" } }
+		Node last { name: "last" ~ }
+	}
+	assert_eq!(log(), ["first", "second", "child", "parent", "last", "deferred"]);
+}
@@ -0,0 +1,34 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative::{block, testing::{construct, mock}};
+
+mock! {
+	Window { set_title(title); }
+	Button { set_label(label); }
+}
+
+#[test]
+fn parent() {
+	block! {
+		Window window {
+			child: &_ @ Button button {
+				'parent parent
+				'parent { set_title: "edited" } // after building the window
+				set_label: "label"
+			}!
+		}
+	}
+	assert!(std::ptr::eq(parent, &window));
+	assert_eq!(window.to_string(), "\
+Window
+  .child(Button
+    set_label(\"label\"))
+  .build()
+  set_title(\"edited\")");
+	
+	assert_eq!(button.to_string(), "Button\n  set_label(\"label\")");
+}
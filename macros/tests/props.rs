@@ -0,0 +1,43 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![allow(unused_variables, dead_code)]
+
+use declarative_macros::Props;
+
+#[derive(Props)]
+struct Test<'a, T: Clone> {
+	required: &'a str,
+	#[prop(default = 6)] spacing: i32,
+	#[prop(optional)] field: Option<T>,
+}
+
+impl<T: Clone> Test<'_, T> {
+	fn start(self) -> i32 { self.spacing * 2 }
+}
+
+#[test]
+fn props() {
+	macro_rules! construct {
+		(? ~$type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build() };
+		(?  $type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build().start() };
+		
+		(? ~$struct_literal:expr) => { $struct_literal };
+		(?  $struct_literal:expr) => { $struct_literal.start() };
+	}
+	
+	declarative_macros::block! {
+		Test::<u8> defaults { required: "text"; .. ~~ }? // does not start
+		Test::<u8> spacing { spacing: 3; required: "text"; .. }? // start
+		Test::<u8> full { required: "text"; spacing: 1; field: Some(2); .. ~~ }? // does not start
+		Test::<u8> literal { required: "text"; spacing: 1; field: None ~~ }? // just a struct literal
+	}
+	
+	assert_eq!((defaults.spacing, defaults.field), (6, None));
+	assert_eq!(spacing, 6);
+	assert_eq!((full.spacing, full.field), (1, Some(2)));
+	assert_eq!(literal.spacing, 1);
+}
@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::block;
+use std::{cell::RefCell, rc::{Rc, Weak}};
+
+#[derive(Clone, Default)] // clones share the text, like widgets
+struct Label(Rc<RefCell<String>>, Rc<Handler>);
+
+type Handler = RefCell<Option<Box<dyn Fn(&str)>>>;
+
+impl Label {
+	fn set_label(&self, label: &str) {
+		*self.0.borrow_mut() = label.into();
+		if let Some(handler) = &*self.1.borrow() { handler(label) } // like a signal
+	}
+	fn label(&self) -> String { self.0.borrow().clone() }
+	fn connect_label(&self, handler: impl Fn(&str) + 'static) { *self.1.borrow_mut() = Some(Box::new(handler)) }
+}
+
+impl declarative::Downgrade for Label {
+	type Weak = (Weak<RefCell<String>>, Weak<Handler>);
+	fn downgrade(&self) -> Self::Weak { (Rc::downgrade(&self.0), Rc::downgrade(&self.1)) }
+	fn upgrade_from(weak: &Self::Weak) -> Option<Self> { Some(Label(weak.0.upgrade()?, weak.1.upgrade()?)) }
+}
+
+#[test]
+fn state() {
+	block! {
+		Label::default() first {
+			'state count: u8 = 1
+			'bind #set_label: &format!("{count}")
+		}
+		Label::default() second { 'bind set_label: &format!("{count} times") }
+		Label::default() third { 'bind: if count.is_multiple_of(2) { set_label: "even" } }
+	}
+	let labels = || [&first, &second, &third].map(Label::label);
+	assert_eq!(labels(), ["1", "", "even"]); // `#` initializes, `:` initializes unconditionally
+	
+	set_count(2);
+	assert_eq!(labels(), ["2", "2 times", "even"]);
+	
+	let mut times = 0;
+	update_count(&mut |count| { *count += 1; times += 1 });
+	assert_eq!(labels(), ["3", "3 times", "even"]);
+	assert_eq!(times, 1);
+}
+
+#[test]
+fn reentrant() {
+	block! {
+		Label::default() label {
+			'state count: u8 = 0
+			'bind set_label: &count.to_string()
+			connect_label: move |text| if text == "1" { set_count(2) } // while refreshing
+		}
+	}
+	update_count(&mut |count| *count = 1);
+	assert_eq!(label.label(), "2");
+	
+	let weak = Rc::downgrade(&label.0);
+	drop(label); // the handler has the setter, which does not keep the label
+	assert!(weak.upgrade().is_none());
+}
@@ -0,0 +1,37 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::view;
+
+#[view(struct = Parts)]
+impl Parts {
+	fn new(name: &str) -> Self {
+		view! { String::from(name) ref mut first { push: '!' } }
+		
+		let second = |suffix: char| {
+			view! { String::new() ref mut second { push_str: name; push: suffix } }
+			expand_view_here! { } // only `second`
+			second
+		};
+		expand_view_here! { } // only `first`
+		Self { first, second: second('?') }
+	}
+	
+	fn echo() -> impl FnMut(&str) -> String {
+		view! { String::new() mut text { 'bind push_str: more } }
+		expand_view_here! { }
+		move |more: &str| { bindings!(); text.clone() }
+	}
+}
+
+#[test]
+fn statements() {
+	let parts = Parts::new("parts");
+	assert_eq!((parts.first.as_str(), parts.second.as_str()), ("parts!", "parts?"));
+	
+	let mut echo = Parts::echo(); // not exported
+	assert_eq!((echo("a"), echo("b")), ("a".into(), "ab".into()));
+}
@@ -0,0 +1,8 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[test]
+fn ui() { trybuild::TestCases::new().compile_fail("tests/ui/*.rs") }
@@ -0,0 +1,42 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[allow(unused_imports)] // the impls are not expanded after these errors
+use declarative::{component::Component, html::{self, construct}};
+
+struct Rootless;
+
+#[declarative_macros::view(component)]
+impl Component for Rootless {
+	type Msg = ();
+	type Init = ();
+	type Root = html::Element;
+	
+	fn init((): ()) -> Self { Self }
+	fn update(&mut self, (): ()) { }
+	
+	view!(html::P paragraph {
+		set_text: &{ let root = "not an item"; root.to_uppercase() }
+	});
+}
+
+struct Statement;
+
+#[declarative_macros::view(component)]
+impl Component for Statement {
+	type Msg = ();
+	type Init = ();
+	type Root = html::Element;
+	
+	fn init((): ()) -> Self {
+		view!(html::P root { });
+		expand_view_here! { }
+		Self
+	}
+	fn update(&mut self, (): ()) { }
+}
+
+fn main() { }
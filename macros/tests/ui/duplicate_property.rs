@@ -0,0 +1,46 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![deny(deprecated)]
+
+#[derive(Default)]
+struct Test;
+
+struct Builder;
+
+impl Test {
+	fn builder() -> Builder { Builder }
+	fn set_label(&self, _: &str) { }
+	fn add_class(&self, _: &str) { }
+}
+
+impl Builder {
+	fn class(self, _: &str) -> Self { self }
+	fn label(self, _: &str) -> Self { self }
+	fn set_label(self, _: &str) -> Self { self }
+	fn build(self) -> Test { Test }
+}
+
+macro_rules! construct {
+	(? $type:ty) => { <$type>::default() };
+	($type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)*.build() };
+}
+
+fn main() {
+	let text = "b";
+	
+	declarative_macros::block! {
+		lint;
+		Test { class: "a"; class: "b"; label: "a"; label: "b" } // chained (`class` adds)
+		Test { add_class: "a"; add_class: "b"; set_label: "a"; set_label: "b" }! // called
+		Test { // refreshed (not duplicated)
+			set_label: "a"
+			'bind #set_label: text
+			'consume refresh = move |text: &str| bindings!()
+		}!
+	}
+	refresh("c");
+}
@@ -0,0 +1,14 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+fn main() {
+	let _ = declarative_macros::block!(=> String::new() first { } String::new() second { });
+	
+	let _ = declarative_macros::block! { // the bindings are not consumed
+		=> text;
+		String::new() mut text { 'bind push_str: "x" }
+	};
+}
@@ -0,0 +1,12 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+fn main() {
+	declarative_macros::block! {
+		#[field(allow(dead_code))] // there is no struct
+		String::new() text { }
+	}
+}
@@ -0,0 +1,29 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::view;
+
+mod module { }
+
+#[view] // the struct would not be declared in `module`
+impl<T> Default for module::Item<T> {
+	fn default() -> Self {
+		expand_view_here! { }
+		Self { text }
+	}
+	view!(String::new() ref text { });
+}
+
+#[view(fields(data: Vec<U>))] // `U` is dropped
+impl<T, U> From<U> for Other<T> {
+	fn from(_: U) -> Self {
+		expand_view_here! { }
+		Self { data: vec![], text }
+	}
+	view!(String::new() ref text { });
+}
+
+fn main() { }
@@ -0,0 +1,36 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::view;
+
+fn make(text: &str) -> String { text.into() }
+
+#[derive(Default)]
+struct List;
+
+impl List {
+	fn push(&mut self, _: u8) -> usize { 1 }
+}
+
+#[view] // the struct is the type of the `impl`, so it cannot have inferred parameters
+impl Parts {
+	fn new() -> Self {
+		expand_view_here! { }
+		let _ = (text, len);
+		Self { typed }
+	}
+	
+	view! {
+		make("text") ref text { }
+		String::new() ref typed { } // deduced from the path
+		
+		List::default() mut list {
+			push: 1 'back ref len { }!
+		}
+	}
+}
+
+fn main() { }
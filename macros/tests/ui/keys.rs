@@ -0,0 +1,16 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[declarative_macros::view(derive(Debug), derive(Clone))]
+mod duplicate { }
+
+#[declarative_macros::view(struct = Parts; String::new() text { })]
+mod followed { }
+
+#[declarative_macros::view(lint, String::new() text { })] // `;` is required before the content
+mod separated { }
+
+fn main() { }
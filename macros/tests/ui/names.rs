@@ -0,0 +1,15 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+fn main() {
+	declarative_macros::block! {
+		String::new() mut text {
+			push_str: &_ @ String::from("a") { }
+		}
+	}
+	// generated names are hygienic, so they are not visible here:
+	let _ = string_from_0;
+}
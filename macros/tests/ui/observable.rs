@@ -0,0 +1,24 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+mod model {
+	#[derive(Default, declarative_macros::Observable)]
+	pub struct Model {
+		pub count: u8,
+		    cache: String, // private, and so are its methods
+		#[observable(changes)] changes: u64,
+	}
+}
+
+#[derive(declarative_macros::Observable)]
+struct Unobserved { count: u8 }
+
+fn main() {
+	let mut model = model::Model::default();
+	model.set_count(1);
+	model.set_cache(String::new());
+	let _ = (model::Model::COUNT_CHANGED, model::Model::CACHE_CHANGED);
+}
@@ -0,0 +1,35 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+struct Node;
+
+struct Builder;
+
+impl Node {
+	fn builder() -> Builder { Builder }
+}
+
+impl Builder {
+	fn after(self, _: &Node) -> Self { self }
+	fn build(self) -> Node { Node }
+}
+
+macro_rules! construct {
+	($type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* .build() }
+}
+
+fn main() {
+	declarative_macros::block! { // circular dependency
+		Node first { after: &second }
+		Node second { after: &first }
+	}
+	
+	declarative_macros::block! { // a reference to a later item in document order
+		order = document;
+		Node early { after: &late }
+		Node late { }
+	}
+}
@@ -0,0 +1,23 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative::{block, testing::{construct, mock}};
+
+mock! {
+	Window { set_title(title); }
+	Button { set_label(label); }
+}
+
+fn main() {
+	block! {
+		Window {
+			child: &_ @ Button {
+				'parent window
+				label: window.to_string() // the window is not built yet
+			}
+		}
+	}
+}
@@ -0,0 +1,26 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative::{construct, Props};
+
+#[derive(Props)]
+struct Greeting {
+	name: &'static str,
+	salute: &'static str,
+	#[prop(optional)] excited: bool,
+}
+
+#[derive(Props)]
+struct Props { value: u8 } // the name of the builder is not taken
+
+fn main() {
+	declarative_macros::block! {
+		Greeting greeting { excited: true; .. ~~ }? // `name` and `salute` are missing
+		Greeting named { name: "world"; .. ~~ }? // only `salute` is missing
+		Props props { value: 1; .. ~~ }?
+	}
+	let _ = (greeting, named, props.value);
+}
@@ -0,0 +1,20 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[derive(Clone)]
+struct Theme(&'static str);
+
+fn child() -> &'static str { "child" }
+
+fn main() {
+	declarative_macros::block! {
+		Vec::new() mut children {
+			push: child()
+			'provide theme = Theme("dark") // after a property
+			push: child()
+		}
+	}
+}
@@ -0,0 +1,23 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+fn main() {
+	declarative_macros::block! {
+		String::new() mut first {
+			push_str: "a"
+			push_str: "x" "y" // first error
+			push_str: "b"
+		}
+		String::new() mut second {
+			'consume // second error
+		}
+		String::new() mut third {
+			push_str: "c"
+		}
+	}
+	// the items that were parsed are still expanded:
+	let _: (String, String, String) = (first, second, third);
+}
@@ -0,0 +1,28 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[derive(Default)]
+struct Label;
+
+impl Label {
+	fn set_label(&self, _: &str) { }
+	fn with(&self, with: impl FnOnce(&Label)) { with(self) }
+}
+
+fn main() {
+	let condition = true;
+	
+	declarative_macros::block! {
+		Label::default() first {
+			if condition { 'state count: u8 = 0 }
+			with: 'with label { 'state other: u8 = 0 }
+		}
+		Label::default() second {
+			'state count: u8 = 0
+			with: 'with label { 'bind set_label: &count.to_string() } // not refreshed
+		}
+	}
+}
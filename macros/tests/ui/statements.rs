@@ -0,0 +1,18 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#[declarative_macros::view(struct = Parts)]
+impl Parts { // no view exports an item
+	fn first() { view!(String::new() _text { }); expand_view_here! { } }
+	fn second() { view!(String::new() _text { }); expand_view_here! { } }
+}
+
+#[declarative_macros::view]
+fn unconsumed() {
+	view!(String::new() _text { });
+}
+
+fn main() { }
@@ -0,0 +1,42 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+struct Node;
+
+struct Builder;
+
+trait Extra { fn extra(&self, _: u8) { } }
+
+impl Extra for Node { }
+
+impl Node {
+	fn builder() -> Builder { Builder }
+}
+
+impl Builder {
+	fn chained(self, _: u8) -> Self { self }
+	fn build(self) -> Node { Node }
+}
+
+#[derive(Default)]
+struct Literal { field: u8 }
+
+macro_rules! construct {
+	(? $literal:expr) => { $literal };
+	($type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* .build() }
+}
+
+fn main() {
+	declarative_macros::block! { // reported without follow-on errors
+		strict;
+		Node strict { chained: 1 #[cfg(all())] chained: 2 Extra::extra &: 3 }
+	}
+	
+	declarative_macros::block! { // struct literals are never deferred
+		Literal literal { field: 1 Extra::extra &: 2 }?
+	}
+	let _ = (strict, literal);
+}
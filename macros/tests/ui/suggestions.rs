@@ -0,0 +1,25 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+struct Test;
+
+impl Test {
+	fn builder() -> Self { Test }
+	fn method(&self) { }
+}
+
+macro_rules! construct {
+	(? $type:ty) => { <$type>::builder() };
+}
+
+fn main() {
+	declarative_macros::block! {
+		lint;
+		Test first { 'bnd method; }!
+		Test second { method; 'consum refresh = || bindings!() }!
+		Test third { method; ~ method; }! // not built
+	}
+}
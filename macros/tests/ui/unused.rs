@@ -0,0 +1,26 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+#![deny(deprecated)]
+
+use declarative_macros::view;
+
+struct Text { text: String, unused: u8 }
+
+#[view(lint)]
+impl Text {
+	fn new() -> Self {
+		view! {
+			String::new() mut text { push_str: &inner } // referenced outside the view
+			String::new() mut unused { push: '!' } // the field of the same name does not count
+			String::new() mut inner { push: '?' } // referenced in the view
+		}
+		expand_view_here! { }
+		Self { text, unused: 0 }
+	}
+}
+
+fn main() { let _ = Text::new().unused; }
@@ -0,0 +1,67 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+use declarative_macros::block;
+use std::cell::RefCell;
+
+#[derive(Default)]
+struct Node(RefCell<Vec<String>>);
+
+struct Builder(Vec<String>);
+
+#[derive(Debug)]
+enum Layout { Vertical }
+
+impl Node {
+	fn builder() -> Builder { Builder(vec![]) }
+	fn add(&self, text: &str) { self.0.borrow_mut().push(text.into()) }
+	
+	fn with_children(&self, children: impl FnOnce(&Node)) {
+		let node = Node::default(); children(&node);
+		self.add(&format!("children: {:?}", node.0.take()))
+	}
+	
+	fn with_layout(&self, layout: Layout, children: impl FnOnce(&Node)) {
+		let node = Node::default(); children(&node);
+		self.add(&format!("{layout:?}: {:?}", node.0.take()))
+	}
+}
+
+impl Builder {
+	fn add(mut self, text: &str) -> Self { self.0.push(text.into()); self }
+	
+	fn with_children(mut self, children: impl FnOnce(&Node)) -> Self {
+		let node = Node::default(); children(&node);
+		self.0.push(format!("children: {:?}", node.0.take())); self
+	}
+	
+	fn build(self) -> Node { Node(RefCell::new(self.0)) }
+}
+
+macro_rules! construct {
+	(? $type:ty) => { <$type>::default() };
+	($type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* .build() }
+}
+
+#[test]
+fn with() {
+	let suffix = String::from("!");
+	
+	block! {
+		Node node {
+			add: "first"
+			with_children: 'with children { add: "a" add: "b" }
+			with_layout: Layout::Vertical 'with move layout { add: &suffix } // after the arguments
+		}!
+		
+		Node built { // a builder argument
+			with_children: 'with children { add: "c" }
+			add: "last"
+		}
+	}
+	assert_eq!(node.0.take(), ["first", r#"children: ["a", "b"]"#, r#"Vertical: ["!"]"#]);
+	assert_eq!(built.0.take(), [r#"children: ["c"]"#, "last"]);
+}
@@ -0,0 +1,248 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+//! Components in the Elm architecture: a state that is updated by messages
+//! and a view that is refreshed after each update, run on any executor.
+//!
+//! When `#[view(component)]` is applied to an `impl Component` with a `view!` and no `fn view`
+//! (or `#[view]` to an `impl declarative::component::Component`), it is generated: the view can
+//! refer to the component as `state` and to its [`Sender`] as `sender`, it must have an item
+//! named `root`, and its bindings refresh it after each update. The generated code refers
+//! to this module as `::declarative::component` unless the `crate` key gives another path.
+//!
+//! Components run with [`run_sync`] (if their messages are `Send`) have a sender whose
+//! [`Sender::sync`] can be moved to other threads, as long as the [`Runtime`] supports it
+//! (like [`Blocking`] and, with the `glib` feature, `Glib`).
+//!
+//! ## Examples
+//!
+//! ~~~
+//! use declarative::{component::{self, Blocking, Component}, html::{self, construct}, view};
+//!
+//! enum Msg { Increase, Reset }
+//!
+//! struct Counter { count: u32 }
+//!
+//! #[view(component)]
+//! impl Component for Counter {
+//!     type Msg = Msg;
+//!     type Init = u32;
+//!     type Root = html::Element;
+//!
+//!     fn init(count: u32) -> Self { Self { count } }
+//!
+//!     fn update(&mut self, msg: Msg) {
+//!         match msg { Msg::Increase => self.count += 1, Msg::Reset => self.count = 0 }
+//!     }
+//!
+//!     view! {
+//!         html::P root {
+//!             'bind #set_text: &format!("The count is {}", state.count)
+//!         }
+//!     }
+//! }
+//!
+//! let runtime = Blocking::default(); // a runtime for tests
+//! let counter = component::run::<Counter>(&runtime, 1);
+//! assert_eq!(counter.root.to_string(), "<p>The count is 1</p>");
+//!
+//! counter.sender.send(Msg::Increase);
+//! counter.sender.send(Msg::Increase);
+//! runtime.run_pending();
+//! assert_eq!(counter.root.to_string(), "<p>The count is 3</p>");
+//! ~~~
+
+use std::{cell::RefCell, rc::Rc, sync::{mpsc, Arc}};
+
+/// A state updated by messages and shown by a view.
+pub trait Component: Sized + 'static {
+	/// The messages that update the state.
+	type Msg: 'static;
+	/// What the state is initialized from.
+	type Init;
+	/// The main object of the view (e.g. a widget).
+	type Root: Clone;
+	
+	/// Creates the state.
+	fn init(init: Self::Init) -> Self;
+	
+	/// Updates the state with a message, after which the view is refreshed.
+	fn update(&mut self, msg: Self::Msg);
+	
+	/// Creates the view of the state, whose objects can send messages with `sender`.
+	fn view(&self, sender: &Sender<Self::Msg>) -> View<Self>;
+}
+
+/// The view of a component: its root and a closure to refresh it.
+pub struct View<C: Component> {
+	/// The main object of the view.
+	pub root: C::Root,
+	/// Called with the state after each update.
+	pub refresh: Box<dyn FnMut(&C)>,
+}
+
+/// Sends messages to a component. Cloning it does not clone the channel.
+pub struct Sender<M>(Rc<dyn Fn(M)>, Option<SyncSender<M>>);
+
+impl<M> Sender<M> {
+	/// Creates a sender from a function that sends a message through some channel.
+	pub fn new(send: impl Fn(M) + 'static) -> Self { Self(Rc::new(send), None) }
+	
+	/// Sends a message to the component.
+	pub fn send(&self, msg: M) { (self.0)(msg) }
+	
+	/// Returns a sender that can be moved to other threads, if the channel allows it
+	/// (i.e. if the component was run with [`run_sync`]).
+	pub fn sync(&self) -> Option<SyncSender<M>> { self.1.clone() }
+}
+
+impl<M> Clone for Sender<M> {
+	fn clone(&self) -> Self { Self(self.0.clone(), self.1.clone()) }
+}
+
+impl<M: 'static> From<SyncSender<M>> for Sender<M> {
+	fn from(sync: SyncSender<M>) -> Self { Self(Rc::new({ let sync = sync.clone(); move |msg| sync.send(msg) }), Some(sync)) }
+}
+
+/// Sends messages to a component from any thread. Cloning it does not clone the channel.
+pub struct SyncSender<M>(Arc<dyn Fn(M) + Send + Sync>);
+
+impl<M> SyncSender<M> {
+	/// Creates a sender from a function that sends a message through a thread-safe channel.
+	pub fn new(send: impl Fn(M) + Send + Sync + 'static) -> Self { Self(Arc::new(send)) }
+	
+	/// Sends a message to the component.
+	pub fn send(&self, msg: M) { (self.0)(msg) }
+}
+
+impl<M> Clone for SyncSender<M> {
+	fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+/// A channel and an executor on which components run.
+pub trait Runtime {
+	/// Creates a channel and spawns a loop that passes each message sent through it to `handle`.
+	fn spawn<M: 'static>(&self, handle: impl FnMut(M) + 'static) -> Sender<M>;
+	
+	/// Like [`Runtime::spawn`], but the returned sender should have a [`SyncSender`] if the channel
+	/// is thread-safe (the default implementation does not).
+	fn spawn_sync<M: Send + 'static>(&self, handle: impl FnMut(M) + 'static) -> Sender<M> { self.spawn(handle) }
+}
+
+/// A running component.
+pub struct Handle<C: Component> {
+	/// The main object of its view.
+	pub root: C::Root,
+	/// Sends messages to it.
+	pub sender: Sender<C::Msg>,
+}
+
+/// Initializes a component, creates its view and spawns its message loop on `runtime`.
+pub fn run<C: Component>(runtime: &impl Runtime, init: C::Init) -> Handle<C> {
+	start(init, |handle| runtime.spawn(handle))
+}
+
+/// Like [`run`], but the sender of the component (also in its view) has a [`SyncSender`]
+/// if the runtime supports it, so that other threads can send it messages.
+pub fn run_sync<C: Component>(runtime: &impl Runtime, init: C::Init) -> Handle<C> where C::Msg: Send {
+	start(init, |handle| runtime.spawn_sync(handle))
+}
+
+/// The state and the refresh closure of a component, or the messages sent before it has a view.
+type Running<C> = Result<(C, Box<dyn FnMut(&C)>), Vec<<C as Component>::Msg>>;
+
+fn start<C: Component>(init: C::Init, spawn: impl FnOnce(Box<dyn FnMut(C::Msg)>) -> Sender<C::Msg>) -> Handle<C> {
+	let running = Rc::new(RefCell::new(Running::<C>::Err(vec![])));
+	
+	let sender = spawn(Box::new({ // the view gets the real sender, so that it keeps the `SyncSender`
+		let running = running.clone();
+		move |msg| match &mut *running.borrow_mut() {
+			Ok((state, refresh)) => { state.update(msg); refresh(state) }
+			Err(queue) => queue.push(msg),
+		}
+	}));
+	let state = C::init(init);
+	let View { root, refresh } = state.view(&sender);
+	
+	if let Err(queue) = running.replace(Ok((state, refresh))) { // handled while the view was created
+		if let (false, Ok((state, refresh))) = (queue.is_empty(), &mut *running.borrow_mut()) {
+			for msg in queue { state.update(msg) }
+			refresh(state)
+		}
+	}
+	Handle { root, sender }
+}
+
+/// A runtime that handles messages on the thread-default main context of GLib
+/// (e.g. that of a GTK application), which must be running on this thread.
+#[cfg(feature = "glib")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Glib;
+
+#[cfg(feature = "glib")]
+impl Glib {
+	fn channel<M: 'static>(mut handle: impl FnMut(M) + 'static) -> async_channel::Sender<M> {
+		let (sender, receiver) = async_channel::unbounded();
+		glib::spawn_future_local(async move { while let Ok(msg) = receiver.recv().await { handle(msg) } });
+		sender
+	}
+}
+
+#[cfg(feature = "glib")]
+impl Runtime for Glib {
+	fn spawn<M: 'static>(&self, handle: impl FnMut(M) + 'static) -> Sender<M> {
+		let sender = Self::channel(handle);
+		Sender::new(move |msg| { let _ = sender.try_send(msg); }) // never full
+	}
+	
+	fn spawn_sync<M: Send + 'static>(&self, handle: impl FnMut(M) + 'static) -> Sender<M> {
+		let sender = Self::channel(handle);
+		SyncSender::new(move |msg| { let _ = sender.try_send(msg); }).into()
+	}
+}
+
+/// A runtime for tests that only uses the standard library:
+/// messages are handled when [`Blocking::run_pending`] is called.
+#[derive(Default)]
+pub struct Blocking { tasks: RefCell<Vec<Box<dyn FnMut() -> bool>>> }
+
+impl Blocking {
+	fn task<M: 'static>(receiver: mpsc::Receiver<M>, mut handle: impl FnMut(M) + 'static) -> Box<dyn FnMut() -> bool> {
+		Box::new(move || {
+			let mut progress = false;
+			while let Ok(msg) = receiver.try_recv() { handle(msg); progress = true }
+			progress
+		})
+	}
+	
+	/// Handles the pending messages of every component, including those sent
+	/// while handling them, and returns when there are no more.
+	pub fn run_pending(&self) {
+		loop {
+			let mut tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+			let progress = tasks.iter_mut().fold(false, |progress, task| task() | progress);
+			
+			let spawned = std::mem::take(&mut *self.tasks.borrow_mut());
+			tasks.extend(spawned);
+			*self.tasks.borrow_mut() = tasks;
+			if !progress { break }
+		}
+	}
+}
+
+impl Runtime for Blocking {
+	fn spawn<M: 'static>(&self, handle: impl FnMut(M) + 'static) -> Sender<M> {
+		let (sender, receiver) = mpsc::channel();
+		self.tasks.borrow_mut().push(Self::task(receiver, handle));
+		Sender::new(move |msg| { let _ = sender.send(msg); })
+	}
+	
+	fn spawn_sync<M: Send + 'static>(&self, handle: impl FnMut(M) + 'static) -> Sender<M> {
+		let (sender, receiver) = mpsc::channel();
+		self.tasks.borrow_mut().push(Self::task(receiver, handle));
+		SyncSender::new(move |msg| { let _ = sender.send(msg); }).into()
+	}
+}
@@ -0,0 +1,83 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+//! Values provided by a view to everything constructed while it is expanded, such as the
+//! views of its child components, without passing them through every constructor.
+//!
+//! `'provide name = expr` (at the beginning of the content) declares `name` and provides a clone
+//! of it until the end of the view expansion, and `'inject name: Type` declares `name` as a clone of the last provided `Type`
+//! (or `'inject name: Type = expr` to fall back to `expr` if none was provided):
+//!
+//! ~~~
+//! use declarative::{block as view, context};
+//!
+//! #[derive(Clone)]
+//! struct Theme { accent: &'static str }
+//!
+//! fn child() -> String {
+//!     view! {
+//!         String::new() mut label {
+//!             'inject theme: Theme
+//!             push_str: theme.accent
+//!         }
+//!     }
+//!     label
+//! }
+//!
+//! view! {
+//!     Vec::new() mut labels {
+//!         'provide theme = Theme { accent: "blue" }
+//!         push: child() // receives the theme
+//!     }
+//! }
+//!
+//! assert_eq!(labels, ["blue"]);
+//! assert!(context::try_inject::<Theme>().is_none()); // no longer provided
+//! ~~~
+
+use std::{any::{Any, TypeId}, cell::{Cell, RefCell}, marker::PhantomData, rc::Rc};
+
+struct Entry { id: usize, ty: TypeId, value: Rc<dyn Any> }
+
+thread_local! {
+	static STACK: RefCell<Vec<Entry>> = const { RefCell::new(vec![]) };
+	static  NEXT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Keeps a value provided until it is dropped.
+#[must_use = "the value is no longer provided when this is dropped"]
+pub struct Provider(usize, PhantomData<*const ()>);
+
+impl Drop for Provider {
+	fn drop(&mut self) {
+		STACK.with_borrow_mut(|stack| stack.retain(|entry| entry.id != self.0))
+	}
+}
+
+/// Provides `value` to [`inject`] (in this thread) until the returned [`Provider`] is dropped,
+/// hiding any other value of the same type provided before.
+pub fn provide<T: Clone + 'static>(value: T) -> Provider {
+	let id = NEXT.replace(NEXT.get() + 1);
+	STACK.with_borrow_mut(|stack| stack.push(Entry { id, ty: TypeId::of::<T>(), value: Rc::new(value) }));
+	Provider(id, PhantomData)
+}
+
+/// Returns a clone of the last value of type `T` provided, if any.
+pub fn try_inject<T: Clone + 'static>() -> Option<T> {
+	let value = STACK.with_borrow(|stack| stack.iter().rev()
+		.find(|entry| entry.ty == TypeId::of::<T>()).map(|entry| entry.value.clone()))?;
+	value.downcast_ref::<T>().cloned() // after the borrow, in case cloning provides or injects
+}
+
+/// Returns a clone of the last value of type `T` provided.
+///
+/// # Panics
+///
+/// If no value of type `T` is provided.
+#[track_caller]
+pub fn inject<T: Clone + 'static>() -> T {
+	try_inject().unwrap_or_else(|| panic!("no value of type `{}` is provided", std::any::type_name::<T>()))
+}
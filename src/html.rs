@@ -0,0 +1,394 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+//! An in-memory HTML tree that can be built with [`block!`](crate::block) and [`view!`](crate::view)
+//! and rendered as an escaped string (for server-side rendering, reports, emails or headless tests).
+//!
+//! Elements are cheap to clone and share the same node, so a `'bind` refresh of an inner element
+//! is seen the next time any of its ancestors is rendered.
+//!
+//! The texts of `<script>` and `<style>` are written verbatim because they are raw text
+//! elements, so together they cannot contain their own closing tag (tags are compared
+//! ignoring ASCII case, as browsers do). Tag and attribute names are not escaped, so they
+//! cannot contain whitespace or any of `"'<>/=`. An element cannot be appended to itself
+//! or to any of its descendants, so the tree has no cycles.
+//!
+//! ## Examples
+//!
+//! ~~~
+//! use declarative::{block as view, html::{self, construct}};
+//!
+//! let mut count = 0;
+//!
+//! view! {
+//!     html::Div root {
+//!         class: "counter"
+//!         child: &_ @ html::H1 { text: "Counter" }
+//!         ~
+//!         append: &_ @ html::P {
+//!             'bind #set_text: &format!("The count is {count} & counting")
+//!         }!
+//!         'consume refresh = move |count| bindings!()
+//!     }
+//! }
+//!
+//! assert_eq!(root.to_string(), "<div class=\"counter\"><h1>Counter</h1>\
+//!     <p>The count is 0 &amp; counting</p></div>");
+//!
+//! count += 1;
+//! refresh(count);
+//!
+//! assert_eq!(root.children()[1].to_string(), "<p>The count is 1 &amp; counting</p>");
+//! ~~~
+
+use std::{borrow::Cow, cell::RefCell, fmt, rc::Rc};
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __html_construct {
+	(? $type:ty) => { <$type as $crate::html::Tag>::element() };
+	
+	(? ~$type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build() };
+	(?  $type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build().start() };
+	
+	(? ~$struct_literal:expr) => { $struct_literal };
+	(?  $struct_literal:expr) => { $struct_literal.start() };
+	
+	(~$builder:expr) => { $builder };
+	( $builder:expr) => { $builder.build() };
+	
+	(~$type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)* };
+	( $type:ty => $($methods:tt)*) => { <$type>::builder() $($methods)*.build() };
+}
+
+#[doc(inline)]
+/// An implementation of `construct!` for the tags of this module.
+/// Must be in scope instead of [`crate::construct!`].
+pub use crate::__html_construct as construct;
+
+/// A node of the tree: an element or a text.
+#[derive(Clone)]
+pub enum Node {
+	/// An element that can have attributes and children.
+	Element(Element),
+	/// A text that is escaped when rendered.
+	Text(Rc<str>),
+}
+
+struct Inner {
+	     tag: Cow<'static, str>,
+	   attrs: Vec<(Cow<'static, str>, String)>,
+	children: Vec<Node>,
+}
+
+/// A shared HTML element. Cloning it does not clone the node.
+#[derive(Clone)]
+pub struct Element(Rc<RefCell<Inner>>);
+
+impl Element {
+	/// Creates an element without attributes or children.
+	///
+	/// # Panics
+	///
+	/// If `tag` is not a valid name (see the [module docs](self)).
+	pub fn new(tag: impl Into<Cow<'static, str>>) -> Self {
+		let tag = tag.into();
+		validate("tag", &tag);
+		Self(Rc::new(RefCell::new(Inner { tag, attrs: vec![], children: vec![] })))
+	}
+	
+	/// Creates a builder of an element with the given tag.
+	pub fn builder(tag: impl Into<Cow<'static, str>>) -> ElementBuilder {
+		ElementBuilder(Self::new(tag))
+	}
+	
+	/// Returns the tag of this element.
+	pub fn tag(&self) -> String { self.0.borrow().tag.to_string() }
+	
+	/// Returns the value of an attribute, if any.
+	pub fn attribute(&self, name: &str) -> Option<String> {
+		self.0.borrow().attrs.iter().find(|(attr, _)| attr == name).map(|(_, value)| value.clone())
+	}
+	
+	/// Returns the children of this element.
+	pub fn children(&self) -> Vec<Node> { self.0.borrow().children.clone() }
+	
+	/// Adds an attribute or replaces its value.
+	///
+	/// # Panics
+	///
+	/// If `name` is not a valid name (see the [module docs](self)).
+	pub fn set_attribute(&self, name: impl Into<Cow<'static, str>>, value: impl Into<String>) {
+		let (name, value) = (name.into(), value.into());
+		validate("attribute", &name);
+		let attrs = &mut self.0.borrow_mut().attrs;
+		
+		match attrs.iter_mut().find(|(attr, _)| *attr == name) {
+			Some((_, old)) => *old = value,
+			None => attrs.push((name, value)),
+		}
+	}
+	
+	/// Removes an attribute.
+	pub fn remove_attribute(&self, name: &str) {
+		self.0.borrow_mut().attrs.retain(|(attr, _)| attr != name)
+	}
+	
+	/// Sets the `id` attribute.
+	pub fn set_id(&self, id: impl Into<String>) { self.set_attribute("id", id) }
+	
+	/// Adds a class to the `class` attribute if it is not there.
+	pub fn add_class(&self, class: &str) {
+		let classes = self.attribute("class").unwrap_or_default();
+		if classes.split_whitespace().any(|name| name == class) { return }
+		
+		let classes = if classes.is_empty() { class.to_owned() } else { format!("{classes} {class}") };
+		self.set_attribute("class", classes)
+	}
+	
+	/// Removes a class from the `class` attribute.
+	pub fn remove_class(&self, class: &str) {
+		let Some(classes) = self.attribute("class") else { return };
+		let classes = classes.split_whitespace().filter(|name| *name != class).collect::<Vec<_>>();
+		self.set_attribute("class", classes.join(" "))
+	}
+	
+	/// Appends a child element.
+	///
+	/// # Panics
+	///
+	/// If `child` is this element or one of its ancestors.
+	pub fn append(&self, child: &Element) {
+		if child.contains(self) { panic!("cannot append an element to itself or to one of its descendants") }
+		self.0.borrow_mut().children.push(Node::Element(child.clone()))
+	}
+	
+	/// Appends a text child.
+	///
+	/// # Panics
+	///
+	/// If this is a `<script>` or `<style>` element and `text` completes its closing tag.
+	pub fn append_text(&self, text: &str) {
+		let mut inner = self.0.borrow_mut();
+		
+		if is(&inner.tag, RAW) { // the texts are written together
+			let mut raw = inner.children.iter()
+				.filter_map(|child| if let Node::Text(text) = child { Some(&**text) } else { None })
+				.collect::<String>();
+			raw.push_str(text);
+			check_raw(&inner.tag, &raw)
+		}
+		inner.children.push(Node::Text(text.into()))
+	}
+	
+	/// Replaces all children with a text.
+	///
+	/// # Panics
+	///
+	/// If this is a `<script>` or `<style>` element and `text` contains its closing tag.
+	pub fn set_text(&self, text: &str) {
+		let mut inner = self.0.borrow_mut();
+		check_raw(&inner.tag, text);
+		inner.children.clear();
+		inner.children.push(Node::Text(text.into()))
+	}
+	
+	/// Removes all children.
+	pub fn clear(&self) { self.0.borrow_mut().children.clear() }
+	
+	fn contains(&self, other: &Element) -> bool {
+		Rc::ptr_eq(&self.0, &other.0) || self.0.borrow().children.iter()
+			.any(|child| matches!(child, Node::Element(child) if child.contains(other)))
+	}
+}
+
+impl fmt::Display for Element {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let inner = self.0.borrow();
+		write!(f, "<{}", inner.tag)?;
+		
+		for (name, value) in &inner.attrs {
+			write!(f, " {name}=\"")?;
+			escape(value, f)?;
+			f.write_str("\"")?
+		}
+		f.write_str(">")?;
+		
+		if is(&inner.tag, VOID) { return Ok(()) }
+		
+		for child in &inner.children {
+			match child {
+				Node::Text(text) if is(&inner.tag, RAW) => f.write_str(text)?,
+				child => child.fmt(f)?
+			}
+		}
+		write!(f, "</{}>", inner.tag)
+	}
+}
+
+impl fmt::Display for Node {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Element(element) => element.fmt(f),
+			Self::Text(text) => escape(text, f),
+		}
+	}
+}
+
+/// Builds an [`Element`] with chained methods.
+pub struct ElementBuilder(Element);
+
+impl ElementBuilder {
+	/// Adds an attribute or replaces its value.
+	pub fn attribute(self, name: impl Into<Cow<'static, str>>, value: impl Into<String>) -> Self {
+		self.0.set_attribute(name, value); self
+	}
+	
+	/// Sets the `id` attribute.
+	pub fn id(self, id: impl Into<String>) -> Self { self.0.set_id(id); self }
+	
+	/// Adds a class to the `class` attribute.
+	pub fn class(self, class: &str) -> Self { self.0.add_class(class); self }
+	
+	/// Appends a child element.
+	pub fn child(self, child: &Element) -> Self { self.0.append(child); self }
+	
+	/// Appends a text child.
+	pub fn text(self, text: &str) -> Self { self.0.append_text(text); self }
+	
+	/// Returns the element.
+	pub fn build(self) -> Element { self.0 }
+}
+
+/// Implemented by the types that represent a tag.
+pub trait Tag {
+	/// The name of the tag.
+	const NAME: &'static str;
+	
+	/// Creates an element with this tag.
+	fn element() -> Element { Element::new(Self::NAME) }
+}
+
+macro_rules! tags {
+	($($type:ident $name:literal)*) => {$(
+		#[doc = concat!("The `<", $name, ">` tag.")]
+		pub struct $type;
+		
+		impl Tag for $type { const NAME: &'static str = $name; }
+		
+		impl $type {
+			/// Creates a builder of an element with this tag.
+			pub fn builder() -> ElementBuilder { Element::builder($name) }
+		}
+	)*};
+}
+
+tags! {
+	A "a" Article "article" Aside "aside" B "b" Body "body" Br "br" Button "button"
+	Code "code" Div "div" Em "em" Footer "footer" Form "form"
+	H1 "h1" H2 "h2" H3 "h3" H4 "h4" H5 "h5" H6 "h6"
+	Head "head" Header "header" Hr "hr" Html "html" I "i" Img "img" Input "input"
+	Label "label" Li "li" Link "link" Main "main" Meta "meta" Nav "nav" Ol "ol"
+	P "p" Pre "pre" Script "script" Section "section" Select "select" Small "small"
+	Span "span" Strong "strong" Style "style" Table "table" Tbody "tbody" Td "td"
+	Textarea "textarea" Th "th" Thead "thead" Title "title" Tr "tr" Ul "ul"
+}
+
+const VOID: &[&str] = &[
+	"area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr"
+];
+
+const RAW: &[&str] = &["script", "style"];
+
+fn validate(kind: &str, name: &str) {
+	if name.is_empty() || name.chars().any(|char| char.is_whitespace() || "\"'<>/=".contains(char)) {
+		panic!("invalid {kind} name: {name:?}")
+	}
+}
+
+fn is(tag: &str, names: &[&str]) -> bool { names.iter().any(|name| tag.eq_ignore_ascii_case(name)) }
+
+fn check_raw(tag: &str, text: &str) {
+	if is(tag, RAW) && text.to_ascii_lowercase().contains(&format!("</{}", tag.to_ascii_lowercase())) {
+		panic!("the text of a <{tag}> element cannot contain `</{tag}`")
+	}
+}
+
+fn escape(text: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	let mut last = 0;
+	
+	for (index, char) in text.char_indices() {
+		let escaped = match char {
+			'&' => "&amp;",
+			'<' => "&lt;",
+			'>' => "&gt;",
+			'"' => "&quot;",
+			'\'' => "&#39;",
+			_ => continue
+		};
+		f.write_str(&text[last..index])?;
+		f.write_str(escaped)?;
+		last = index + 1
+	}
+	f.write_str(&text[last..])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	
+	#[test]
+	fn escaping() {
+		let div = Div::builder().attribute("title", "\"a\" & 'b'").text("<b>1 > 0</b>").build();
+		assert_eq!(div.to_string(), "<div title=\"&quot;a&quot; &amp; &#39;b&#39;\">&lt;b&gt;1 &gt; 0&lt;/b&gt;</div>");
+	}
+	
+	#[test]
+	fn void_elements() {
+		let img = Img::builder().attribute("src", "a.png").build();
+		let br = Br::element();
+		br.append_text("ignored");
+		
+		assert_eq!(P::builder().child(&img).child(&br).build().to_string(), "<p><img src=\"a.png\"><br></p>");
+	}
+	
+	#[test]
+	fn raw_text() {
+		let style = Style::builder().text("a > b { color: red }").build();
+		let script = Script::builder().text("if (a < b && c) {}").build();
+		
+		assert_eq!(style.to_string(), "<style>a > b { color: red }</style>");
+		assert_eq!(script.to_string(), "<script>if (a < b && c) {}</script>");
+	}
+	
+	#[test]
+	#[should_panic = "cannot contain `</script`"]
+	fn raw_text_closing_tag() { Script::element().set_text("</SCRIPT><p>") }
+	
+	#[test]
+	#[should_panic = "cannot contain `</SCRIPT`"]
+	fn raw_text_split_closing_tag() {
+		let script = Element::new("SCRIPT"); // raw regardless of case
+		script.append_text("</scr");
+		script.append_text("ipt><p>")
+	}
+	
+	#[test]
+	#[should_panic = "cannot append an element to itself or to one of its descendants"]
+	fn cycle() {
+		let (div, p) = (Div::element(), P::element());
+		div.append(&p);
+		p.append(&div)
+	}
+	
+	#[test]
+	#[should_panic = "invalid tag name"]
+	fn invalid_tag() { Element::new("p onclick=alert(1)"); }
+	
+	#[test]
+	#[should_panic = "invalid attribute name"]
+	fn invalid_attribute() { Div::element().set_attribute("a=\"b\" onclick", "") }
+}
@@ -8,7 +8,13 @@
 
 #![warn(missing_docs)]
 
-pub use declarative::{block, view};
+pub mod component;
+pub mod context;
+pub mod html;
+pub mod reactive;
+pub mod testing;
+
+pub use declarative::{block, view, Observable, Props};
 
 #[macro_export]
 /// A default implementation for a macro called by [`block!`] and [`view!`].
@@ -16,6 +22,9 @@ pub use declarative::{block, view};
 macro_rules! construct {
 	(? $type:ty) => { <$type>::default() };
 	
+	(? ~$type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build() };
+	(?  $type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build().start() };
+	
 	(? ~$struct_literal:expr) => { $struct_literal };
 	(?  $struct_literal:expr) => { $struct_literal.start() };
 	
@@ -97,3 +106,57 @@ macro_rules! clone {
 		})+)
 	};
 }
+
+/// A reference that can be downgraded to a weak one, so that the closures that refresh the
+/// bindings of a `'state` do not keep their items alive.
+///
+/// It is implemented for [`Rc`](std::rc::Rc) and [`Arc`](std::sync::Arc), and for the types that
+/// implement `glib::clone::Downgrade` (such as widgets) with the `glib` feature. Items whose
+/// bindings mention a `'state` must implement it:
+/// ~~~
+/// use {declarative::Downgrade, std::rc::Rc};
+///
+/// let shared = Rc::new(2);
+/// let weak = shared.downgrade();
+/// assert_eq!(Rc::upgrade_from(&weak), Some(shared));
+/// ~~~
+pub trait Downgrade: Sized {
+	/// The weak reference.
+	type Weak;
+	
+	/// Creates a weak reference to the value.
+	fn downgrade(&self) -> Self::Weak;
+	
+	/// Gets the value back unless it was dropped.
+	fn upgrade_from(weak: &Self::Weak) -> Option<Self>;
+}
+
+#[doc(hidden)] // for the generated code, where the type of the value is unknown
+pub struct WeakRef<T: Downgrade>(T::Weak);
+
+impl<T: Downgrade> WeakRef<T> {
+	pub fn new(value: &T) -> Self { WeakRef(value.downgrade()) }
+	pub fn upgrade(&self) -> Option<T> { T::upgrade_from(&self.0) }
+}
+
+#[cfg(not(feature = "glib"))]
+impl<T: ?Sized> Downgrade for std::rc::Rc<T> {
+	type Weak = std::rc::Weak<T>;
+	fn downgrade(&self) -> Self::Weak { std::rc::Rc::downgrade(self) }
+	fn upgrade_from(weak: &Self::Weak) -> Option<Self> { weak.upgrade() }
+}
+
+#[cfg(not(feature = "glib"))]
+impl<T: ?Sized> Downgrade for std::sync::Arc<T> {
+	type Weak = std::sync::Weak<T>;
+	fn downgrade(&self) -> Self::Weak { std::sync::Arc::downgrade(self) }
+	fn upgrade_from(weak: &Self::Weak) -> Option<Self> { weak.upgrade() }
+}
+
+#[cfg(feature = "glib")] // which also covers `Rc` and `Arc`
+impl<T: glib::clone::Downgrade> Downgrade for T
+where <T as glib::clone::Downgrade>::Weak: glib::clone::Upgrade<Strong = T> {
+	type Weak = <T as glib::clone::Downgrade>::Weak;
+	fn downgrade(&self) -> Self::Weak { glib::clone::Downgrade::downgrade(self) }
+	fn upgrade_from(weak: &Self::Weak) -> Option<Self> { glib::clone::Upgrade::upgrade(weak) }
+}
@@ -0,0 +1,294 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+//! Single-threaded reactive primitives that do not depend on any toolkit.
+//!
+//! A [`Signal`] holds a value, a [`Memo`] derives one from signals (or other memos) and an
+//! [`Effect`] runs a closure. Memos and effects track the signals they read each time they run,
+//! and run again when any of them changes. Changes made inside [`batch`] (or inside a running
+//! effect) are applied together, so that each dependent runs once at the end.
+//!
+//! `'consume name => Effect::new` expands to `let name = Effect::new(move || bindings!())`,
+//! so the bindings run once and then whenever the signals they read change:
+//!
+//! ~~~
+//! use declarative::{block as view, html::{self, construct}, reactive::{Effect, Signal}};
+//!
+//! let count = Signal::new(0);
+//! let counter = count.clone();
+//!
+//! view! {
+//!     html::Div root {
+//!         class: "counter"
+//!         ~
+//!         append: &_ @ html::P {
+//!             'bind set_text: &format!("The count is {}", count.get())
+//!         }!
+//!         'consume effect => Effect::new // dropping `effect` stops it
+//!     }
+//! }
+//!
+//! assert_eq!(root.to_string(), "<div class=\"counter\"><p>The count is 0</p></div>");
+//!
+//! counter.update(|count| *count += 1);
+//! assert_eq!(root.to_string(), "<div class=\"counter\"><p>The count is 1</p></div>");
+//! ~~~
+
+use std::{cell::{Cell, RefCell}, fmt, rc::{Rc, Weak}};
+
+struct Computation {
+	      run: RefCell<Box<dyn FnMut()>>,
+	     memo: bool,
+	scheduled: Cell<bool>,
+	     runs: Cell<usize>, // to ignore the subscriptions of previous runs
+}
+
+thread_local! {
+	static OBSERVER: RefCell<Option<Weak<Computation>>> = const { RefCell::new(None) };
+	static  PENDING: RefCell<Vec<Rc<Computation>>> = const { RefCell::new(vec![]) };
+	static    DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+impl Computation {
+	fn new(memo: bool, run: impl FnMut() + 'static) -> Rc<Self> {
+		let computation = Rc::new(Self {
+			run: RefCell::new(Box::new(run)), memo, scheduled: Cell::new(false), runs: Cell::new(0)
+		});
+		batch(|| computation.run());
+		computation
+	}
+	
+	fn run(self: &Rc<Self>) {
+		self.scheduled.set(false);
+		self.runs.set(self.runs.get() + 1);
+		let _observer = Observer(OBSERVER.replace(Some(Rc::downgrade(self))));
+		(self.run.borrow_mut())()
+	}
+}
+
+/// Restores the previous observer, even if the computation panics.
+struct Observer(Option<Weak<Computation>>);
+
+impl Drop for Observer {
+	fn drop(&mut self) { OBSERVER.set(self.0.take()) }
+}
+
+/// Leaves a batch, even if it panics (otherwise no other batch would apply its changes).
+struct Depth;
+
+impl Drop for Depth {
+	fn drop(&mut self) { DEPTH.set(DEPTH.get() - 1) }
+}
+
+/// Applies every change made in `changes` at the end, so that each memo or effect
+/// that depends on the changed signals runs once (nested batches wait for the outermost).
+pub fn batch<R>(changes: impl FnOnce() -> R) -> R {
+	DEPTH.set(DEPTH.get() + 1);
+	let _depth = Depth;
+	let result = changes();
+	
+	if DEPTH.get() == 1 {
+		loop { // memos first so that effects do not see outdated values
+			let next = PENDING.with_borrow_mut(|pending| {
+				let index = pending.iter().position(|computation| computation.memo).unwrap_or(0);
+				(!pending.is_empty()).then(|| pending.swap_remove(index))
+			});
+			let Some(computation) = next else { break };
+			if computation.scheduled.get() { computation.run() }
+		}
+	}
+	result
+}
+
+struct Subscribers(RefCell<Vec<(Weak<Computation>, usize)>>); // with the run that subscribed
+
+impl Subscribers {
+	fn track(&self) {
+		let Some(observer) = OBSERVER.with_borrow(Clone::clone) else { return };
+		let Some(runs) = observer.upgrade().map(|computation| computation.runs.get()) else { return };
+		let mut subscribers = self.0.borrow_mut();
+		
+		match subscribers.iter_mut().find(|(subscriber, _)| subscriber.ptr_eq(&observer)) {
+			Some((_, run)) => *run = runs,
+			None => subscribers.push((observer, runs)),
+		}
+	}
+	
+	fn notify(&self) {
+		let subscribers = std::mem::take(&mut *self.0.borrow_mut()); // they subscribe again when they run
+		
+		batch(|| PENDING.with_borrow_mut(|pending| pending.extend(subscribers.iter()
+			.filter_map(|(subscriber, run)| subscriber.upgrade().filter(|computation| computation.runs.get() == *run))
+			.filter(|computation| !computation.scheduled.replace(true))
+		)))
+	}
+}
+
+/// A value whose readers are notified when it changes. Cloning it does not clone the value.
+pub struct Signal<T>(Rc<(RefCell<T>, Subscribers)>);
+
+impl<T> Signal<T> {
+	/// Creates a signal with an initial value.
+	pub fn new(value: T) -> Self { Self(Rc::new((RefCell::new(value), Subscribers(RefCell::new(vec![]))))) }
+	
+	/// Returns a clone of the value, subscribing the running memo or effect.
+	pub fn get(&self) -> T where T: Clone { self.with(T::clone) }
+	
+	/// Reads the value by reference, subscribing the running memo or effect.
+	pub fn with<R>(&self, read: impl FnOnce(&T) -> R) -> R {
+		self.0.1.track();
+		read(&self.0.0.borrow())
+	}
+	
+	/// Returns a clone of the value without subscribing anything.
+	pub fn get_untracked(&self) -> T where T: Clone { self.0.0.borrow().clone() }
+	
+	/// Replaces the value and notifies its readers.
+	pub fn set(&self, value: T) { self.update(|old| *old = value) }
+	
+	/// Modifies the value in place and notifies its readers.
+	pub fn update(&self, update: impl FnOnce(&mut T)) {
+		update(&mut self.0.0.borrow_mut());
+		self.0.1.notify()
+	}
+}
+
+impl<T> Clone for Signal<T> {
+	fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Signal<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("Signal").field(&self.0.0.borrow()).finish()
+	}
+}
+
+/// A value computed from signals or other memos, which is only recomputed when they
+/// change and only notifies its readers if the result is different. Cloning it is cheap.
+pub struct Memo<T>(Signal<T>, Rc<Computation>);
+
+impl<T: PartialEq + 'static> Memo<T> {
+	/// Computes the initial value immediately.
+	pub fn new(compute: impl Fn() -> T + 'static) -> Self {
+		let cell = Rc::new(std::cell::OnceCell::<Signal<T>>::new());
+		
+		let computation = Computation::new(true, {
+			let cell = cell.clone();
+			move || {
+				let value = compute();
+				match cell.get() {
+					Some(signal) => if *signal.0.0.borrow() != value { signal.set(value) }
+					None => { let _ = cell.set(Signal::new(value)); }
+				}
+			}
+		});
+		Self(cell.get().unwrap().clone(), computation)
+	}
+}
+
+impl<T> Memo<T> {
+	/// Returns a clone of the value, subscribing the running memo or effect.
+	pub fn get(&self) -> T where T: Clone { self.0.get() }
+	
+	/// Reads the value by reference, subscribing the running memo or effect.
+	pub fn with<R>(&self, read: impl FnOnce(&T) -> R) -> R { self.0.with(read) }
+}
+
+impl<T> Clone for Memo<T> {
+	fn clone(&self) -> Self { Self(self.0.clone(), self.1.clone()) }
+}
+
+/// A closure that runs immediately and then whenever a signal or memo it read changes.
+/// It stops when dropped, unless [`Effect::forget`] is called.
+pub struct Effect(Rc<Computation>);
+
+impl Effect {
+	/// Runs `effect` immediately to track what it reads.
+	pub fn new(effect: impl FnMut() + 'static) -> Self { Self(Computation::new(false, effect)) }
+	
+	/// Runs the effect again, even if nothing it read has changed.
+	pub fn run(&self) { batch(|| self.0.run()) }
+	
+	/// Keeps running the effect for the rest of the thread.
+	pub fn forget(self) { std::mem::forget(self) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	
+	fn counter() -> (Rc<Cell<usize>>, impl Fn()) {
+		let runs = Rc::new(Cell::new(0));
+		(runs.clone(), move || runs.set(runs.get() + 1))
+	}
+	
+	#[test]
+	fn equal_memo() {
+		let (signal, (runs, count)) = (Signal::new(1), counter());
+		let memo = Memo::new({ let signal = signal.clone(); move || signal.get() % 2 });
+		let _effect = Effect::new({ let memo = memo.clone(); move || { memo.get(); count() } });
+		
+		signal.set(3); // same parity
+		assert_eq!((memo.get(), runs.get()), (1, 1));
+		
+		signal.set(4);
+		assert_eq!((memo.get(), runs.get()), (0, 2));
+	}
+	
+	#[test]
+	fn coalescing() {
+		let (a, b, (runs, count)) = (Signal::new(0), Signal::new(0), counter());
+		let _effect = Effect::new({ let (a, b) = (a.clone(), b.clone()); move || { let _ = a.get() + b.get(); count() } });
+		
+		batch(|| { a.set(1); b.set(1); a.set(2) });
+		assert_eq!(runs.get(), 2);
+	}
+	
+	#[test]
+	fn dropped_effect() {
+		let (signal, (runs, count)) = (Signal::new(0), counter());
+		let effect = Effect::new({ let signal = signal.clone(); move || { signal.get(); count() } });
+		
+		signal.set(1);
+		drop(effect);
+		signal.set(2);
+		assert_eq!(runs.get(), 2);
+	}
+	
+	#[test]
+	fn conditional_dependencies() {
+		let (condition, a, b, (runs, count)) = (Signal::new(true), Signal::new(0), Signal::new(0), counter());
+		
+		let _effect = Effect::new({
+			let (condition, a, b) = (condition.clone(), a.clone(), b.clone());
+			move || { let _ = if condition.get() { a.get() } else { b.get() }; count() }
+		});
+		b.set(1); // not read yet
+		assert_eq!(runs.get(), 1);
+		
+		condition.set(false);
+		a.set(1); // no longer read
+		assert_eq!(runs.get(), 2);
+		
+		b.set(2);
+		assert_eq!(runs.get(), 3);
+	}
+	
+	#[test]
+	fn panics() {
+		let (signal, (runs, count)) = (Signal::new(0), counter());
+		
+		let _effect = Effect::new({
+			let signal = signal.clone();
+			move || { if signal.get() == 1 { panic!("effect") } count() }
+		});
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| signal.set(1))).is_err());
+		assert!(OBSERVER.with_borrow(Option::is_none));
+		
+		signal.set(2); // still flushed
+		assert_eq!(runs.get(), 2);
+	}
+}
@@ -0,0 +1,349 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Eduardo Javier Alvarado Aarón <eduardo.javier.alvarado.aaron@gmail.com>
+ *
+ * SPDX-License-Identifier: (Apache-2.0 or MIT)
+ */
+
+//! Mock objects that record what a view does with them, to test view code without a display.
+//!
+//! Types declared with [`mock!`] wrap a [`Recorder`], which keeps in order the builder calls,
+//! field assignments (of struct literal items) and method calls of the object, so that a view can
+//! be checked as a tree, including what `'bind` refreshes and `'back` edits do after construction.
+//!
+//! ## Examples
+//!
+//! ~~~
+//! use declarative::{block as view, testing::{construct, mock}};
+//!
+//! mock! {
+//!     Window { set_child(child); }
+//!     Label { set_text(text); }
+//! }
+//!
+//! let mut count = 0;
+//!
+//! view! {
+//!     Window window {
+//!         title: "Counter"
+//!         ~
+//!         set_child: &_ @ Label {
+//!             'bind #set_text: format!("The count is {count}")
+//!         }!
+//!         'consume refresh = move |count| bindings!()
+//!     }
+//! }
+//!
+//! count += 1;
+//! refresh(count);
+//!
+//! assert_eq!(window.to_string(), "\
+//! Window
+//!   .title(\"Counter\")
+//!   .build()
+//!   set_child(Label
+//!     set_text(\"The count is 0\")
+//!     set_text(\"The count is 1\"))");
+//! ~~~
+
+use std::{cell::RefCell, fmt, rc::Rc};
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __testing_construct {
+	(? $type:ty) => { <$type as $crate::testing::Mock>::new() };
+	
+	(? ~$type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build() };
+	(?  $type:ty { $($field:ident: $value:expr,)* .. }) => { <$type>::props() $(.$field($value))* .build().start() };
+	
+	(? ~$type:ty { $($field:ident: $value:expr),* $(,)? }) => {{
+		let mock = <$type as $crate::testing::Mock>::new();
+		$($crate::testing::Mock::recorder(&mock).record($crate::testing::Event::Field {
+			 name: stringify!($field),
+			value: $crate::testing::Arg::value(&$value),
+		});)*
+		mock
+	}};
+	(? $type:ty { $($fields:tt)* }) => {{
+		let mock = $crate::testing::construct!(? ~$type { $($fields)* });
+		$crate::testing::Mock::recorder(&mock).call("start", vec![]);
+		mock
+	}};
+	
+	(~$builder:expr) => { $builder };
+	( $builder:expr) => { $builder.build() };
+	
+	(~$type:ty => $(.$method:ident($($arg:expr),* $(,)?))*) => {{
+		let mock = <$type as $crate::testing::Mock>::new();
+		$($crate::testing::Mock::recorder(&mock).record($crate::testing::Event::Builder {
+			method: stringify!($method),
+			  args: vec![$($crate::testing::Arg::value(&$arg)),*],
+		});)*
+		mock
+	}};
+	($type:ty => $($methods:tt)*) => {
+		$crate::testing::construct!(~$type => $($methods)*.build())
+	};
+}
+
+#[doc(inline)]
+/// An implementation of `construct!` for the types declared with [`mock!`].
+/// Must be in scope instead of [`crate::construct!`].
+pub use crate::__testing_construct as construct;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __testing_mock {
+	($($vis:vis $name:ident { $($method:ident($($arg:ident),* $(,)?) $(-> $back:ty)?;)* })*) => {$(
+		#[derive(Clone)]
+		$vis struct $name($crate::testing::Recorder);
+		
+		impl $crate::testing::Mock for $name {
+			fn new() -> Self { Self($crate::testing::Recorder::new(stringify!($name))) }
+			fn recorder(&self) -> &$crate::testing::Recorder { &self.0 }
+		}
+		
+		impl $crate::testing::Arg for $name {
+			fn value(&self) -> $crate::testing::Value { $crate::testing::Value::Mock(self.0.clone()) }
+		}
+		
+		impl ::core::fmt::Display for $name {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result { self.0.fmt(f) }
+		}
+		
+		#[allow(dead_code)]
+		impl $name {$(
+			$vis fn $method(&self, $($arg: impl $crate::testing::Arg),*) $(-> $back)? {
+				let _call = self.0.call(stringify!($method), vec![$($crate::testing::Arg::value(&$arg)),*]);
+				$(return _call.back::<$back>();)?
+			}
+		)*}
+	)*};
+}
+
+#[doc(inline)]
+/// Declares types that record their method calls (all arguments must implement [`Arg`]).
+///
+/// A method can return another mock, which is recorded with the call (useful for `'back`).
+///
+/// ~~~
+/// declarative::testing::mock! {
+///     pub Stack { add_child(child) -> Page; }
+///     pub Page { set_title(title); }
+///     Label { set_text(text); set_selectable(selectable); }
+/// }
+/// ~~~
+pub use crate::__testing_mock as mock;
+
+/// Implemented by the types declared with [`mock!`].
+pub trait Mock {
+	/// Creates the object with an empty record.
+	fn new() -> Self;
+	/// Returns the record of the object.
+	fn recorder(&self) -> &Recorder;
+}
+
+/// A value given to a mock, as a builder argument, a field or a method argument.
+#[derive(Clone)]
+pub enum Value {
+	/// The `Debug` representation of a value that is not a mock.
+	Debug(String),
+	/// A mock (composition).
+	Mock(Recorder),
+}
+
+/// Implemented by the values that can be given to a mock.
+pub trait Arg {
+	/// Returns what is recorded for this value.
+	fn value(&self) -> Value;
+}
+
+macro_rules! debug_args {
+	($($type:ty)*) => {$(
+		impl Arg for $type { fn value(&self) -> Value { Value::Debug(format!("{self:?}")) } }
+	)*};
+}
+
+debug_args!(() bool char f32 f64 i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize str String);
+
+impl<T: Arg + ?Sized> Arg for &T {
+	fn value(&self) -> Value { T::value(self) }
+}
+
+impl<T: Arg + ?Sized> Arg for &mut T {
+	fn value(&self) -> Value { T::value(self) }
+}
+
+impl<T: Arg> Arg for Option<T> {
+	fn value(&self) -> Value {
+		match self {
+			Some(value) => match T::value(value) {
+				Value::Debug(value) => Value::Debug(format!("Some({value})")),
+				value => value,
+			}
+			None => Value::Debug("None".into()),
+		}
+	}
+}
+
+impl Arg for Recorder {
+	fn value(&self) -> Value { Value::Mock(self.clone()) }
+}
+
+/// Something done with a mock.
+#[derive(Clone)]
+pub enum Event {
+	/// A method chained to the builder of the mock.
+	Builder {
+		/// The name of the method.
+		method: &'static str,
+		/// The arguments of the method.
+		  args: Vec<Value>,
+	},
+	/// A field of a struct literal item.
+	Field {
+		/// The name of the field.
+		 name: &'static str,
+		/// The value of the field.
+		value: Value,
+	},
+	/// A method called on the mock.
+	Call {
+		/// The name of the method.
+		method: &'static str,
+		/// The arguments of the method.
+		  args: Vec<Value>,
+		/// The mock returned by the method, if any.
+		  back: Option<Recorder>,
+	},
+}
+
+struct Record { name: &'static str, events: Vec<Event> }
+
+/// The shared record of a mock. Cloning it does not clone the record.
+///
+/// It is displayed as a tree, one event per line, with the mocks given as arguments nested
+/// (a mock given to one of its descendants is displayed as `Name (cycle)`).
+#[derive(Clone)]
+pub struct Recorder(Rc<RefCell<Record>>);
+
+impl Recorder {
+	/// Creates an empty record of an object.
+	pub fn new(name: &'static str) -> Self {
+		Self(Rc::new(RefCell::new(Record { name, events: vec![] })))
+	}
+	
+	/// Returns the name of the recorded object.
+	pub fn name(&self) -> &'static str { self.0.borrow().name }
+	
+	/// Returns the events recorded until now.
+	pub fn events(&self) -> Vec<Event> { self.0.borrow().events.clone() }
+	
+	/// Records an event.
+	pub fn record(&self, event: Event) { self.0.borrow_mut().events.push(event) }
+	
+	/// Records a method call whose return can be recorded with [`Call::back`].
+	pub fn call(&self, method: &'static str, args: Vec<Value>) -> Call<'_> {
+		self.record(Event::Call { method, args, back: None });
+		Call(self)
+	}
+	
+	fn write(&self, f: &mut fmt::Formatter<'_>, path: &mut Vec<*const RefCell<Record>>) -> fmt::Result {
+		let record = self.0.borrow();
+		f.write_str(record.name)?;
+		
+		if path.contains(&Rc::as_ptr(&self.0)) { return f.write_str(" (cycle)") }
+		path.push(Rc::as_ptr(&self.0));
+		
+		for event in &record.events {
+			write!(f, "\n{:1$}", "", path.len() * 2)?;
+			
+			match event {
+				Event::Builder { method, args } => {
+					write!(f, ".{method}(")?; write_args(args, f, path)?; f.write_str(")")?
+				}
+				Event::Field { name, value } => {
+					write!(f, "{name}: ")?; write_value(value, f, path)?
+				}
+				Event::Call { method, args, back } => {
+					write!(f, "{method}(")?; write_args(args, f, path)?; f.write_str(")")?;
+					if let Some(back) = back { f.write_str(" -> ")?; back.write(f, path)? }
+				}
+			}
+		}
+		path.pop();
+		Ok(())
+	}
+}
+
+impl fmt::Display for Recorder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.write(f, &mut vec![]) }
+}
+
+impl Mock for Recorder {
+	fn new() -> Self { Self::new("Recorder") }
+	fn recorder(&self) -> &Recorder { self }
+}
+
+/// The last method call recorded on a mock.
+pub struct Call<'a>(&'a Recorder);
+
+impl Call<'_> {
+	/// Creates the mock returned by the call and records it.
+	pub fn back<T: Mock>(self) -> T {
+		let back = T::new();
+		
+		if let Some(Event::Call { back: returned, .. }) = self.0.0.borrow_mut().events.last_mut() {
+			*returned = Some(back.recorder().clone())
+		}
+		back
+	}
+}
+
+fn write_value(value: &Value, f: &mut fmt::Formatter<'_>, path: &mut Vec<*const RefCell<Record>>) -> fmt::Result {
+	match value {
+		Value::Debug(value) => f.write_str(value),
+		Value::Mock(mock) => mock.write(f, path),
+	}
+}
+
+fn write_args(args: &[Value], f: &mut fmt::Formatter<'_>, path: &mut Vec<*const RefCell<Record>>) -> fmt::Result {
+	for (index, arg) in args.iter().enumerate() {
+		if index > 0 { f.write_str(", ")? }
+		write_value(arg, f, path)?
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	
+	#[test]
+	fn cycle() {
+		let (parent, child) = (Recorder::new("Parent"), Recorder::new("Child"));
+		parent.call("add", vec![child.value()]);
+		child.call("add", vec![parent.value()]);
+		parent.call("add", vec![child.value()]); // shared but not a cycle here
+		
+		assert_eq!(parent.to_string(), "Parent\n  add(Child\n    add(Parent (cycle)))\n  add(Child\n    add(Parent (cycle)))");
+	}
+	
+	#[test]
+	fn props() {
+		mock! { Label { set_text(text); } }
+		
+		#[derive(crate::Props)]
+		struct Text { text: &'static str, #[prop(default = "!")] suffix: &'static str }
+		
+		impl Text {
+			fn start(self) -> Label { let label = Label::new(); label.set_text(self.text.to_owned() + self.suffix); label }
+		}
+		
+		crate::block! {
+			Text label { text: "Hello"; .. }?
+			Text text { text: "Hello"; suffix: "."; .. ~~ }?
+		}
+		assert_eq!(label.to_string(), "Label\n  set_text(\"Hello!\")");
+		assert_eq!(text.suffix, ".");
+	}
+}